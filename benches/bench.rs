@@ -1,7 +1,7 @@
 #![feature(test)]
 extern crate test;
 
-use gitjournal::GitJournal;
+use gitjournal::{Config, GitJournal, ParseOptions, Parser};
 use test::Bencher;
 
 #[bench]
@@ -14,13 +14,64 @@ fn verify_huge_message(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn verify_huge_message_body_length(b: &mut Bencher) {
+    let mut journal = GitJournal::new(".").unwrap();
+    journal.config.max_body_lines = Some(100);
+    journal.config.max_body_line_length = Some(120);
+    b.iter(|| {
+        journal
+            .verify("./tests/commit_messages/success_huge")
+            .is_ok()
+    });
+}
+
 #[bench]
 fn parse(b: &mut Bencher) {
     let mut journal = GitJournal::new(".").unwrap();
     journal.config.enable_debug = false;
     b.iter(|| {
         journal
-            .parse_log("HEAD", "rc", 0, true, false, None, None)
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok()
+    });
+}
+
+#[bench]
+fn parse_many_list_items(b: &mut Bencher) {
+    let mut config = Config::new();
+    config.categories = (0..200).map(|i| format!("Category{}", i)).collect();
+    let parser = Parser::new(config);
+    let mut message = "Category0 Add many list items\n\n".to_owned();
+    for i in 0..500 {
+        message += &format!("- Category{} Change the item {}\n", i % 200, i);
+    }
+    b.iter(|| parser.parse_commit_message(&message, None).is_ok());
+}
+
+#[bench]
+fn print_template_serial(b: &mut Bencher) {
+    print_template(b, false);
+}
+
+#[bench]
+fn print_template_parallel(b: &mut Bencher) {
+    print_template(b, true);
+}
+
+fn print_template(b: &mut Bencher, render_parallel: bool) {
+    let mut journal = GitJournal::new(".").unwrap();
+    let mut config = journal.config.clone();
+    config.enable_debug = false;
+    config.render_parallel = render_parallel;
+    journal.set_config(config);
+    journal
+        .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+        .unwrap();
+    let output = std::env::temp_dir().join("gitjournal_bench.md");
+    b.iter(|| {
+        journal
+            .print_log(false, Some("./tests/template.toml"), output.to_str())
             .is_ok()
     });
 }
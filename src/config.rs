@@ -1,11 +1,23 @@
 //! Everything related to the git-journal configuration. The configuration
 //! files are stored in [toml](https://github.com/toml-lang/toml) format with the file name `.gitjournal.toml`.
 
-use failure::{format_err, Error};
+use crate::verify::{ProblemKind, Severity};
+use chrono::{Date, FixedOffset, Local, Offset, TimeZone, Utc};
+use failure::{bail, format_err, Error};
 use lazy_static::lazy_static;
 use log::info;
+use regex::{Regex, RegexBuilder};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_derive::{Deserialize, Serialize};
-use std::{fs::File, io::prelude::*, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt,
+    fs::File,
+    io::prelude::*,
+    path::{Path, PathBuf},
+};
+use term::color::{self, Color};
 
 /// The configuration structure for git-journal.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,37 +25,385 @@ pub struct Config {
     /// Specifies the available categories for the commit message
     pub categories: Vec<String>,
 
-    /// Set the characters where the categories are wrapped in
-    pub category_delimiters: Vec<String>,
+    /// Set the pairs of characters where the categories are wrapped in, where
+    /// the first pair will be used for the output. A single pair like
+    /// `["[", "]"]` is accepted as well.
+    #[serde(deserialize_with = "Config::deserialize_category_delimiters")]
+    pub category_delimiters: Vec<(String, String)>,
+
+    /// The separator of a scope within the category delimiters, like
+    /// `[Fixed:parser]`, where an empty one disables the scope parsing
+    #[serde(default = "Config::get_default_category_scope_separator")]
+    pub category_scope_separator: String,
 
     /// Set to false if the output should not be colored
     pub colored_output: bool,
 
+    /// Use colors within the terminal "always", "never" or "auto" (default),
+    /// which detects if the output is a TTY
+    #[serde(default = "Config::get_default_color")]
+    pub color: String,
+
+    /// The commit message style: "default" for the RFC0001 based messages or
+    /// "conventional" for `type(scope): text` like summaries
+    #[serde(default = "Config::get_default_commit_style")]
+    pub commit_style: String,
+
+    /// Scopes which will be listed within the prepared conventional commit
+    /// message template
+    #[serde(default)]
+    pub known_scopes: Vec<String>,
+
     /// Specifies the default template. Will be used for tag validation and
     /// printing.
     pub default_template: Option<String>,
 
+    /// Use the tags of the default template as categories instead of the
+    /// configured ones
+    #[serde(default)]
+    pub categories_from_template: bool,
+
+    /// Warn about the tags of the parsed commits which are not part of the
+    /// default template, since they will not appear in its output
+    #[serde(default)]
+    pub warn_unmapped_tags: bool,
+
+    /// Categories whose commits need a non empty body, e.g. "Removed"
+    #[serde(default)]
+    pub require_body_for: Vec<String>,
+
+    /// Set to false to allow commit summaries without a category, which can
+    /// be inferred from the changed paths afterwards
+    #[serde(default = "Config::get_default_require_category")]
+    pub require_category: bool,
+
+    /// Use the first line of commit messages with an unparsable summary as
+    /// summary without a category instead of failing, which helps to
+    /// generate changelogs of non conforming histories
+    #[serde(default)]
+    pub lenient_summary: bool,
+
     /// Show or hide the debug messages like `[OKAY] ...` or `[INFO] ...`
     pub enable_debug: bool,
 
+    /// Strip simple inline markdown like `code` or **bold** from the commit
+    /// text on terminal output, file output keeps it untouched
+    #[serde(default)]
+    pub escape_terminal_markdown: bool,
+
+    /// Render markdown links like `[text](url)` on terminal output: "off"
+    /// (default), "url" for `text (url)` or "text" for the link text only
+    #[serde(default = "Config::get_default_render_markdown_links")]
+    pub render_markdown_links: String,
+
+    /// Escape the commit text within the markdown output, like a leading `#`
+    /// which would turn it into a heading, whereas inline markdown like
+    /// `code`, **bold** or links is kept. If unset, only the output files and
+    /// the GitHub release bodies are escaped, whereas terminal output is never
+    /// escaped.
+    #[serde(default)]
+    pub escape_markdown: Option<bool>,
+
     /// Excluded tags in an array, e.g. "internal"
     pub excluded_commit_tags: Vec<String>,
 
+    /// Excluded commits in an array, where abbreviated hashes are possible
+    #[serde(default)]
+    pub exclude_commits: Vec<String>,
+
+    /// Include the commits which are meant to be autosquashed, like
+    /// `fixup! Fixed a bug`, within the changelog
+    #[serde(default)]
+    pub include_fixup_commits: bool,
+
+    /// Promote the categorized list items of squash merge commits to
+    /// standalone entries, which allows asterisks as list markers as well
+    #[serde(default)]
+    pub expand_squash_commits: bool,
+
     /// Enable or disable the output and accumulation of commit footers
     pub enable_footers: bool,
 
+    /// The rendering of the footers: "plain" (default), "bold" for bold keys
+    /// or "table" for a markdown table
+    #[serde(default = "Config::get_default_footer_style")]
+    pub footer_style: String,
+
+    /// A regex to detect the footer lines instead of the default `Key: value`
+    /// ones, where the first capture group is the key and the second one the
+    /// value, e.g. `^(Fixes|Closes) (#\d+)$`
+    #[serde(default)]
+    pub footer_pattern: Option<FooterPattern>,
+
+    /// The key of a trailer which overrides the category of the summary,
+    /// e.g. "Category" for `Category: Fixed`
+    #[serde(default)]
+    pub category_override_trailer: Option<String>,
+
+    /// The document flavor of the changelog: "markdown" (default), "gitlab",
+    /// which wraps the template sections in collapsible `<details>` blocks, or
+    /// "rst" for reStructuredText. Not to be confused with the
+    /// `--output-format` command line option, which selects the kind of the
+    /// output, like terminal or JSON.
+    #[serde(default = "Config::get_default_document_format")]
+    pub document_format: String,
+
+    /// Append the changelog to an existing output file instead of
+    /// overwriting it, like the `--append` command line flag
+    #[serde(default)]
+    pub append_output: bool,
+
+    /// Collapse only template sections with more entries than this threshold
+    /// when using the "gitlab" output format
+    #[serde(default)]
+    pub collapse_threshold: usize,
+
+    /// Highlight commits with a security footer in a separate section on top
+    /// of every release
+    #[serde(default)]
+    pub highlight_security: bool,
+
+    /// The footer key which marks security relevant commits
+    #[serde(default = "Config::get_default_security_footer_key")]
+    pub security_footer_key: String,
+
     /// Show or hide the commit hash for every entry
     pub show_commit_hash: bool,
 
+    /// The indentation of nested list items and paragraphs, which will be
+    /// halved for the template based output
+    #[serde(default = "Config::get_default_indent_width")]
+    pub indent_width: usize,
+
+    /// The bullet character of the list entries: "-" (default), "*" or "+",
+    /// where reStructuredText output always uses "*"
+    #[serde(
+        default = "Config::get_default_bullet_char",
+        deserialize_with = "Config::deserialize_bullet_char"
+    )]
+    pub bullet_char: String,
+
+    /// Wrap the summary text of the entries at this column, where the
+    /// continuation lines are aligned under the text
+    #[serde(default)]
+    pub summary_wrap_width: Option<usize>,
+
+    /// Show the git notes of the commits below the entries in detailed mode
+    #[serde(default)]
+    pub show_notes: bool,
+
+    /// The git notes reference which will be used for `show_notes`
+    #[serde(default = "Config::get_default_notes_ref")]
+    pub notes_ref: String,
+
+    /// Show the files changed by the commits below the entries in detailed
+    /// mode
+    #[serde(default)]
+    pub show_files_changed: bool,
+
+    /// The maximum number of files shown per commit for `show_files_changed`,
+    /// where the remaining ones are summarized as "+N more"
+    #[serde(default = "Config::get_default_max_files_changed")]
+    pub max_files_changed: usize,
+
+    /// Mark signed commits with a `✓ (unverified)` and unsigned ones with a
+    /// ✗, where only the existence of a signature is checked
+    #[serde(default)]
+    pub show_signature_status: bool,
+
+    /// Show the number of printed commits within the version header
+    #[serde(default)]
+    pub show_commit_count: bool,
+
+    /// Print a table of contents with links to every version header on top of
+    /// the markdown output
+    #[serde(default)]
+    pub generate_toc: bool,
+
+    /// The title of the whole changelog like "Changelog", which is printed
+    /// once on top of the output
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Render the tags of the template based output in parallel, which does
+    /// not apply to the colored terminal output
+    #[serde(default = "Config::get_default_render_parallel")]
+    pub render_parallel: bool,
+
     /// Show or hide the commit message prefix, e.g. JIRA-1234
     pub show_prefix: bool,
 
-    /// Sort the commits during the output by "date" (default) or "name"
+    /// Path to a newline separated word list, which will be used to spell
+    /// check the commit summary during verification
+    #[serde(default)]
+    pub spellcheck_wordlist: Option<String>,
+
+    /// Fail the verification on unknown words instead of only warning
+    #[serde(default)]
+    pub spellcheck_strict: bool,
+
+    /// Warn if the summary text after the category does not start with a
+    /// verb in imperative mood
+    #[serde(default)]
+    pub imperative_mood_check: bool,
+
+    /// Word endings which indicate a non imperative mood, e.g. "ed"
+    #[serde(default = "Config::get_default_imperative_mood_suffixes")]
+    pub imperative_mood_suffixes: Vec<String>,
+
+    /// Words which are accepted even if they end with one of the suffixes,
+    /// e.g. "process"
+    #[serde(default = "Config::get_default_imperative_mood_exceptions")]
+    pub imperative_mood_exceptions: Vec<String>,
+
+    /// Words or phrases which are not allowed within the commit message, e.g.
+    /// "WIP". They match case insensitive on whole words only.
+    #[serde(default)]
+    pub disallowed_phrases: Vec<String>,
+
+    /// Treat the `disallowed_phrases` as regular expressions instead
+    #[serde(default)]
+    pub disallowed_phrases_regex: bool,
+
+    /// The maximum number of lines of the commit message body, where comment
+    /// lines are not counted
+    #[serde(default)]
+    pub max_body_lines: Option<usize>,
+
+    /// The maximum number of characters of every commit message body line
+    #[serde(default)]
+    pub max_body_line_length: Option<usize>,
+
+    /// Fix the trailing whitespace and a final period of the summary as well
+    /// as the category casing of the summary and list items within the
+    /// commit-msg hook before the verification
+    #[serde(default)]
+    pub autofix: bool,
+
+    /// Sort the commits during the output by "date" (default) or "name",
+    /// which sorts by the category and then by the summary text
     pub sort_by: String,
 
+    /// The direction of the "date" sorting: "descending" (default) or
+    /// "ascending"
+    #[serde(default = "Config::get_default_sort_direction")]
+    pub sort_direction: String,
+
+    /// The order of the commits within a tag: "newest" (default) first or
+    /// "oldest" first, which reverses the sorted commits
+    #[serde(default = "Config::get_default_commit_order")]
+    pub commit_order: String,
+
+    /// Group the commits by "tag" (default), by "month" with sections named
+    /// like `2024-03` or by the summary "prefix" like `JIRA-1234`, where
+    /// commits without a prefix are grouped as "Other". The git tags are
+    /// ignored for the monthly and prefix groups.
+    #[serde(default = "Config::get_default_group_by")]
+    pub group_by: String,
+
+    /// The name of a release if multiple tags point to the same commit:
+    /// "semver" (default) prefers the first semantic version like tag,
+    /// whereas "merge" joins all of them like `v2.0.0, latest`
+    #[serde(default = "Config::get_default_tag_preference")]
+    pub tag_preference: String,
+
+    /// The date of a release: "commit" (default) for the date of the tagged
+    /// commit or "tag" for the tagger date of annotated tags
+    #[serde(default = "Config::get_default_release_date_source")]
+    pub release_date_source: String,
+
+    /// The timezone of the release dates: "utc" (default), "local" or a fixed
+    /// offset like "+02:00"
+    #[serde(default)]
+    pub timezone: Timezone,
+
+    /// Skip the commits without any relation to a git tag by default, like
+    /// the `--skip-unreleased` command line flag
+    #[serde(default)]
+    pub skip_unreleased: bool,
+
+    /// Parse the whole history by default instead of stopping at the tags,
+    /// like the `--all` command line flag. An explicitly given tags count
+    /// takes precedence.
+    #[serde(default)]
+    pub parse_all: bool,
+
+    /// Group the commits of the detailed output without a template in
+    /// sections per category
+    #[serde(default)]
+    pub group_by_category: bool,
+
+    /// Categories where the commit body is printed within the short output
+    /// as well, like "Removed" to explain the removal
+    #[serde(default)]
+    pub always_expand_categories: Vec<String>,
+
+    /// Print the category of consecutive list items only once as a sub-header
+    /// instead of in front of every item
+    #[serde(default)]
+    pub collapse_repeated_list_categories: bool,
+
+    /// Append a single line paragraph right after the summary to the summary
+    /// line instead of printing it as a separate block
+    #[serde(default)]
+    pub treat_first_paragraph_as_subtitle: bool,
+
+    /// The separator between the summary and its subtitle for
+    /// `treat_first_paragraph_as_subtitle`
+    #[serde(default = "Config::get_default_subtitle_separator")]
+    pub subtitle_separator: String,
+
     /// Commit message template prefix which will be added during commit
     /// preparation
     pub template_prefix: String,
+
+    /// The prefix of comment lines within the commit messages, which is
+    /// read from `core.commentChar` of the git configuration instead of the
+    /// configuration file
+    #[serde(skip, default = "Config::get_default_comment_char")]
+    pub comment_char: String,
+
+    /// Show the emoji of the `category_emoji` mapping in front of the
+    /// categories within the output
+    #[serde(default)]
+    pub show_category_emoji: bool,
+
+    /// The emoji or shortcodes of the categories, e.g. `Added = "✨"` or
+    /// `Fixed = ":bug:"`
+    #[serde(default)]
+    pub category_emoji: BTreeMap<String, String>,
+
+    /// The displayed names of the categories within the output, e.g.
+    /// `Added = "New Features"`, where unmapped ones are printed as they are
+    #[serde(default)]
+    pub category_headings: BTreeMap<String, String>,
+
+    /// The displayed labels of the footer keys within the output, e.g.
+    /// `Reviewed-by = "Reviewed by"`, where unmapped ones are printed as they
+    /// are
+    #[serde(default)]
+    pub footer_labels: BTreeMap<String, String>,
+
+    /// Categories of commits without a category, which will be used if all
+    /// changed paths of the commit match the path glob, e.g.
+    /// `"tests/*" = "Tested"`
+    #[serde(default)]
+    pub infer_category_from_paths: BTreeMap<String, String>,
+
+    /// The commit message templates of the branches matching the glob, e.g.
+    /// `[branch_templates."hotfix/*"]`, where the first match will be used
+    /// during the commit preparation
+    #[serde(default)]
+    pub branch_templates: BTreeMap<String, BranchTemplate>,
+
+    /// The colors of the terminal output, like `header = "bright_red"`
+    #[serde(default)]
+    pub colors: Colors,
+
+    /// Overwrite the severity ("warn", "error" or "off") of the verification
+    /// rules, e.g. `imperative_mood = "error"`
+    #[serde(default)]
+    pub verify_severity: BTreeMap<String, Severity>,
 }
 
 impl Config {
@@ -59,19 +419,138 @@ impl Config {
     pub fn new() -> Self {
         Self {
             categories: Self::get_default_categories(),
-            category_delimiters: vec!["[".to_owned(), "]".to_owned()],
+            category_delimiters: vec![("[".to_owned(), "]".to_owned())],
+            category_scope_separator: Self::get_default_category_scope_separator(),
             colored_output: true,
+            color: Self::get_default_color(),
+            commit_style: Self::get_default_commit_style(),
+            known_scopes: vec![],
             default_template: None,
+            categories_from_template: false,
+            warn_unmapped_tags: false,
+            require_body_for: vec![],
+            require_category: Self::get_default_require_category(),
+            lenient_summary: false,
             enable_debug: true,
+            escape_terminal_markdown: false,
+            render_markdown_links: Self::get_default_render_markdown_links(),
+            escape_markdown: None,
             excluded_commit_tags: vec![],
+            exclude_commits: vec![],
+            include_fixup_commits: false,
+            expand_squash_commits: false,
             enable_footers: false,
+            footer_style: Self::get_default_footer_style(),
+            footer_pattern: None,
+            category_override_trailer: None,
+            document_format: Self::get_default_document_format(),
+            append_output: false,
+            collapse_threshold: 0,
+            highlight_security: false,
+            security_footer_key: Self::get_default_security_footer_key(),
             show_commit_hash: false,
+            indent_width: Self::get_default_indent_width(),
+            bullet_char: Self::get_default_bullet_char(),
+            summary_wrap_width: None,
+            show_notes: false,
+            notes_ref: Self::get_default_notes_ref(),
+            show_files_changed: false,
+            max_files_changed: Self::get_default_max_files_changed(),
+            show_signature_status: false,
+            show_commit_count: false,
+            generate_toc: false,
+            title: None,
+            render_parallel: Self::get_default_render_parallel(),
             show_prefix: false,
+            spellcheck_wordlist: None,
+            spellcheck_strict: false,
+            imperative_mood_check: false,
+            imperative_mood_suffixes: Self::get_default_imperative_mood_suffixes(),
+            imperative_mood_exceptions: Self::get_default_imperative_mood_exceptions(),
+            disallowed_phrases: vec![],
+            disallowed_phrases_regex: false,
+            max_body_lines: None,
+            max_body_line_length: None,
+            autofix: false,
             sort_by: "date".to_owned(),
+            sort_direction: Self::get_default_sort_direction(),
+            commit_order: Self::get_default_commit_order(),
+            group_by: Self::get_default_group_by(),
+            tag_preference: Self::get_default_tag_preference(),
+            release_date_source: Self::get_default_release_date_source(),
+            timezone: Timezone::Utc,
+            skip_unreleased: false,
+            parse_all: false,
+            group_by_category: false,
+            always_expand_categories: vec![],
+            collapse_repeated_list_categories: false,
+            treat_first_paragraph_as_subtitle: false,
+            subtitle_separator: Self::get_default_subtitle_separator(),
             template_prefix: "JIRA-1234".to_owned(),
+            comment_char: Self::get_default_comment_char(),
+            show_category_emoji: false,
+            category_emoji: BTreeMap::new(),
+            category_headings: BTreeMap::new(),
+            footer_labels: BTreeMap::new(),
+            infer_category_from_paths: BTreeMap::new(),
+            branch_templates: BTreeMap::new(),
+            colors: Colors::default(),
+            verify_severity: BTreeMap::new(),
+        }
+    }
+
+    fn deserialize_category_delimiters<'de, D>(
+        deserializer: D,
+    ) -> Result<Vec<(String, String)>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum CategoryDelimiters {
+            Pairs(Vec<(String, String)>),
+            Pair((String, String)),
+        }
+
+        let pairs = match CategoryDelimiters::deserialize(deserializer)? {
+            CategoryDelimiters::Pairs(pairs) => pairs,
+            CategoryDelimiters::Pair(pair) => vec![pair],
+        };
+        if pairs.is_empty() {
+            return Err(de::Error::custom(
+                "expected at least one pair of category delimiters",
+            ));
+        }
+        if let Some((open, close)) = pairs
+            .iter()
+            .find(|(open, close)| open.is_empty() || close.is_empty())
+        {
+            return Err(de::Error::custom(format!(
+                "invalid category delimiters ('{}', '{}'), expected non empty strings",
+                open, close
+            )));
+        }
+        Ok(pairs)
+    }
+
+    fn deserialize_bullet_char<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bullet_char = String::deserialize(deserializer)?;
+        match bullet_char.as_str() {
+            "-" | "*" | "+" => Ok(bullet_char),
+            _ => Err(de::Error::custom(format!(
+                "invalid bullet character '{}', expected '-', '*' or '+'",
+                bullet_char
+            ))),
         }
     }
 
+    fn get_default_category_scope_separator() -> String {
+        ":".to_owned()
+    }
+
     fn get_default_categories() -> Vec<String> {
         vec![
             "Added".to_owned(),
@@ -82,6 +561,166 @@ impl Config {
         ]
     }
 
+    fn get_default_require_category() -> bool {
+        true
+    }
+
+    fn get_default_commit_style() -> String {
+        "default".to_owned()
+    }
+
+    fn get_default_footer_style() -> String {
+        "plain".to_owned()
+    }
+
+    fn get_default_render_markdown_links() -> String {
+        "off".to_owned()
+    }
+
+    fn get_default_document_format() -> String {
+        "markdown".to_owned()
+    }
+
+    fn get_default_indent_width() -> usize {
+        4
+    }
+
+    fn get_default_subtitle_separator() -> String {
+        " — ".to_owned()
+    }
+
+    fn get_default_bullet_char() -> String {
+        "-".to_owned()
+    }
+
+    fn get_default_notes_ref() -> String {
+        "refs/notes/commits".to_owned()
+    }
+
+    fn get_default_max_files_changed() -> usize {
+        10
+    }
+
+    fn get_default_render_parallel() -> bool {
+        true
+    }
+
+    fn get_default_color() -> String {
+        "auto".to_owned()
+    }
+
+    fn get_default_group_by() -> String {
+        "tag".to_owned()
+    }
+
+    fn get_default_tag_preference() -> String {
+        "semver".to_owned()
+    }
+
+    fn get_default_commit_order() -> String {
+        "newest".to_owned()
+    }
+
+    fn get_default_release_date_source() -> String {
+        "commit".to_owned()
+    }
+
+    fn get_default_sort_direction() -> String {
+        "descending".to_owned()
+    }
+
+    fn get_default_security_footer_key() -> String {
+        "Security".to_owned()
+    }
+
+    fn get_default_comment_char() -> String {
+        "#".to_owned()
+    }
+
+    fn get_default_imperative_mood_suffixes() -> Vec<String> {
+        vec!["ed".to_owned(), "ing".to_owned(), "s".to_owned()]
+    }
+
+    fn get_default_imperative_mood_exceptions() -> Vec<String> {
+        vec![
+            "access".to_owned(),
+            "address".to_owned(),
+            "bring".to_owned(),
+            "bypass".to_owned(),
+            "discuss".to_owned(),
+            "embed".to_owned(),
+            "express".to_owned(),
+            "focus".to_owned(),
+            "need".to_owned(),
+            "pass".to_owned(),
+            "process".to_owned(),
+            "speed".to_owned(),
+        ]
+    }
+
+    /// Check if a line of a commit message is a comment, which will be
+    /// removed by git.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::Config;
+    /// let mut config = Config::new();
+    /// assert!(config.is_comment_line("# A comment"));
+    ///
+    /// config.comment_char = ";".to_owned();
+    /// assert!(config.is_comment_line("; A comment"));
+    /// assert!(!config.is_comment_line("# Some text"));
+    /// ```
+    #[must_use]
+    pub fn is_comment_line(&self, line: &str) -> bool {
+        !self.comment_char.is_empty() && line.starts_with(&self.comment_char)
+    }
+
+    /// The date of a unix timestamp within the configured `timezone`, which is
+    /// used for the release dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{FixedOffset, TimeZone, Utc};
+    /// use gitjournal::{config::Timezone, Config};
+    ///
+    /// let mut config = Config::new();
+    /// let seconds = Utc.ymd(2020, 1, 2).and_hms(23, 30, 0).timestamp();
+    /// assert_eq!(config.date_of(seconds), Utc.ymd(2020, 1, 2));
+    ///
+    /// config.timezone = Timezone::Fixed(FixedOffset::east(2 * 3600));
+    /// assert_eq!(config.date_of(seconds), Utc.ymd(2020, 1, 3));
+    /// ```
+    #[must_use]
+    pub fn date_of(&self, seconds: i64) -> Date<Utc> {
+        let offset = match self.timezone {
+            Timezone::Utc => FixedOffset::east(0),
+            Timezone::Local => Local.timestamp(seconds, 0).offset().fix(),
+            Timezone::Fixed(offset) => offset,
+        };
+        Utc.from_utc_date(&offset.timestamp(seconds, 0).date().naive_local())
+    }
+
+    /// Get the configured severity of a verification rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{Config, ProblemKind, Severity};
+    /// let config = Config::new();
+    /// assert_eq!(config.get_severity(ProblemKind::Parse), Severity::Error);
+    /// ```
+    #[must_use]
+    pub fn get_severity(&self, kind: ProblemKind) -> Severity {
+        match self.verify_severity.get(kind.name()) {
+            Some(severity) => *severity,
+            None if kind == ProblemKind::Spelling && self.spellcheck_strict => Severity::Error,
+            None => kind.default_severity(),
+        }
+    }
+
     /// Save the default configuration file in a certain path.
     ///
     /// # Examples
@@ -129,8 +768,8 @@ impl Config {
         let mut toml_string = String::new();
         file.read_to_string(&mut toml_string)?;
 
-        // Deserialize the toml string
-        *self = toml::from_str(&toml_string)?;
+        // Deserialize the toml string, some editors write a leading BOM
+        *self = toml::from_str(toml_string.trim_start_matches('\u{feff}'))?;
 
         // If the categories are not found within the toml it will return an
         // empty array which will break the parser. So use the default
@@ -166,9 +805,329 @@ impl Config {
     }
 }
 
+/// The commit message template of a branch, where unset values fall back to
+/// the default ones.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BranchTemplate {
+    /// Commit message template prefix which replaces the `template_prefix`
+    pub template_prefix: Option<String>,
+
+    /// The categories which replace the `categories` within the template
+    pub categories: Option<Vec<String>>,
+}
+
+/// The colors of the different parts of the terminal output, which are
+/// configured by their names, like `"bright_blue"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Colors {
+    /// The color of the tag names
+    #[serde(
+        serialize_with = "Colors::serialize_color",
+        deserialize_with = "Colors::deserialize_color"
+    )]
+    pub tag: Color,
+
+    /// The color of the tag dates
+    #[serde(
+        serialize_with = "Colors::serialize_color",
+        deserialize_with = "Colors::deserialize_color"
+    )]
+    pub date: Color,
+
+    /// The color of the categories
+    #[serde(
+        serialize_with = "Colors::serialize_color",
+        deserialize_with = "Colors::deserialize_color"
+    )]
+    pub category: Color,
+
+    /// The color of the text after the categories
+    #[serde(
+        serialize_with = "Colors::serialize_color",
+        deserialize_with = "Colors::deserialize_color"
+    )]
+    pub text: Color,
+
+    /// The color of the section headers, like the ones of the footers
+    #[serde(
+        serialize_with = "Colors::serialize_color",
+        deserialize_with = "Colors::deserialize_color"
+    )]
+    pub header: Color,
+}
+
+/// The names of the supported terminal colors.
+const COLOR_NAMES: [(&str, Color); 16] = [
+    ("black", color::BLACK),
+    ("red", color::RED),
+    ("green", color::GREEN),
+    ("yellow", color::YELLOW),
+    ("blue", color::BLUE),
+    ("magenta", color::MAGENTA),
+    ("cyan", color::CYAN),
+    ("white", color::WHITE),
+    ("bright_black", color::BRIGHT_BLACK),
+    ("bright_red", color::BRIGHT_RED),
+    ("bright_green", color::BRIGHT_GREEN),
+    ("bright_yellow", color::BRIGHT_YELLOW),
+    ("bright_blue", color::BRIGHT_BLUE),
+    ("bright_magenta", color::BRIGHT_MAGENTA),
+    ("bright_cyan", color::BRIGHT_CYAN),
+    ("bright_white", color::BRIGHT_WHITE),
+];
+
+impl Colors {
+    /// Returns the terminal color of a name like `"bright_blue"`, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::config::Colors;
+    /// assert_eq!(Colors::parse("bright_blue"), Some(term::color::BRIGHT_BLUE));
+    /// assert_eq!(Colors::parse("purple"), None);
+    /// ```
+    pub fn parse(name: &str) -> Option<Color> {
+        COLOR_NAMES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, color)| *color)
+    }
+
+    fn serialize_color<S>(color: &Color, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match COLOR_NAMES.iter().find(|(_, known)| known == color) {
+            Some((name, _)) => serializer.serialize_str(name),
+            None => serializer.serialize_u32(*color),
+        }
+    }
+
+    fn deserialize_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Self::parse(&name).ok_or_else(|| {
+            de::Error::custom(format!(
+                "unknown color '{}', expected one of 'black', 'red', 'green', 'yellow', 'blue', \
+                 'magenta', 'cyan', 'white' or their 'bright_' variants",
+                name
+            ))
+        })
+    }
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Self {
+            tag: color::GREEN,
+            date: color::YELLOW,
+            category: color::BRIGHT_BLUE,
+            text: color::WHITE,
+            header: color::BRIGHT_RED,
+        }
+    }
+}
+
+/// A compiled regex to detect the footer lines of the commit messages, where
+/// the first capture group is the key and the second one the value.
+#[derive(Debug, Clone)]
+pub struct FooterPattern(Regex);
+
+impl FooterPattern {
+    /// Compiles a footer pattern, which matches on every line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::config::FooterPattern;
+    /// assert!(FooterPattern::new(r"^(Fixes|Closes) (#\d+)$").is_ok());
+    /// assert!(FooterPattern::new(r"^Fixes (#\d+)$").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// When the pattern is not a valid regex or has less than two capture
+    /// groups.
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let regex = RegexBuilder::new(pattern).multi_line(true).build()?;
+
+        // The first group is the implicit one of the whole match
+        if regex.captures_len() < 3 {
+            bail!(
+                "Footer pattern '{}' needs a capture group for the key and the value",
+                pattern
+            );
+        }
+        Ok(Self(regex))
+    }
+
+    /// The compiled regex of the pattern.
+    pub fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl PartialEq for FooterPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Serialize for FooterPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FooterPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(de::Error::custom)
+    }
+}
+
+/// The timezone of the release dates.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Timezone {
+    /// The coordinated universal time
+    #[default]
+    Utc,
+
+    /// The local timezone of the system
+    Local,
+
+    /// A fixed offset to the coordinated universal time
+    Fixed(FixedOffset),
+}
+
+impl Timezone {
+    /// Parses "utc", "local" or a fixed offset like "+02:00", "+0200" or
+    /// "+02", where the names are case insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::FixedOffset;
+    /// use gitjournal::config::Timezone;
+    /// assert_eq!(Timezone::parse("Local"), Some(Timezone::Local));
+    /// assert_eq!(
+    ///     Timezone::parse("-0530"),
+    ///     Some(Timezone::Fixed(FixedOffset::west(5 * 3600 + 30 * 60)))
+    /// );
+    /// assert_eq!(Timezone::parse("Europe/Berlin"), None);
+    /// ```
+    pub fn parse(timezone: &str) -> Option<Self> {
+        if timezone.eq_ignore_ascii_case("utc") {
+            return Some(Timezone::Utc);
+        }
+        if timezone.eq_ignore_ascii_case("local") {
+            return Some(Timezone::Local);
+        }
+        let (sign, offset) = match timezone.strip_prefix('+') {
+            Some(offset) => (1, offset),
+            None => (-1, timezone.strip_prefix('-')?),
+        };
+        let digits = offset.replacen(':', "", 1);
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let (hours, minutes) = match digits.len() {
+            2 => (digits.parse::<i32>().ok()?, 0),
+            4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+            _ => return None,
+        };
+        if minutes >= 60 {
+            return None;
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Timezone::Fixed)
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Timezone::Utc => write!(f, "utc"),
+            Timezone::Local => write!(f, "local"),
+            Timezone::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+impl Serialize for Timezone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timezone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let timezone = String::deserialize(deserializer)?;
+        Self::parse(&timezone).ok_or_else(|| {
+            de::Error::custom(format!(
+                "invalid timezone '{}', expected 'utc', 'local' or an offset like '+02:00'",
+                timezone
+            ))
+        })
+    }
+}
+
+/// Resolves the path of the default template within the given path, where
+/// the configured one takes precedence over the environment variable value.
+pub(crate) fn resolve_template(
+    path: &Path,
+    configured: Option<&str>,
+    env_value: Option<OsString>,
+) -> Option<PathBuf> {
+    let template = match configured {
+        Some(template) => PathBuf::from(template),
+        None => PathBuf::from(env_value?),
+    };
+    Some(path.join(template))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{
+        env, fs, process,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// A unique temporary directory, which will be removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let path = env::temp_dir().join(format!(
+                "gitjournal_config_{}_{}",
+                process::id(),
+                COUNT.fetch_add(1, Ordering::SeqCst)
+            ));
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
 
     #[test]
     fn config_save_and_load_ok() {
@@ -178,6 +1137,35 @@ mod tests {
         assert_eq!(config.is_default_config(), true);
     }
 
+    #[test]
+    fn config_load_with_bom() {
+        let dir = TempDir::new();
+        let toml_string = toml::to_string(&Config::new()).unwrap();
+        fs::write(
+            dir.0.join(".gitjournal.toml"),
+            format!("\u{feff}{}", toml_string),
+        )
+        .unwrap();
+
+        let mut config = Config::new();
+        config.colored_output = false;
+        assert!(config.load(dir.0.to_str().unwrap()).is_ok());
+        assert_eq!(config.is_default_config(), true);
+    }
+
+    #[test]
+    fn config_load_skip_unreleased() {
+        let dir = TempDir::new();
+        let toml_string = toml::to_string(&Config::new())
+            .unwrap()
+            .replace("skip_unreleased = false", "skip_unreleased = true");
+        fs::write(dir.0.join(".gitjournal.toml"), toml_string).unwrap();
+
+        let mut config = Config::new();
+        assert!(config.load(dir.0.to_str().unwrap()).is_ok());
+        assert!(config.skip_unreleased);
+    }
+
     #[test]
     fn config_save_err() {
         let config = Config::new();
@@ -197,6 +1185,165 @@ mod tests {
         }
     }
 
+    /// Loads a configuration which contains only the required fields, where
+    /// the `extra` fields are added or override them.
+    fn load_config_with(extra: &str) -> Result<Config, toml::de::Error> {
+        let mut table: toml::value::Table = toml::from_str(
+            r#"
+            categories = ["Added", "Changed", "Fixed", "Improved", "Removed"]
+            category_delimiters = ["[", "]"]
+            colored_output = true
+            enable_debug = true
+            excluded_commit_tags = []
+            enable_footers = false
+            show_commit_hash = false
+            show_prefix = false
+            sort_by = "date"
+            template_prefix = "JIRA-1234"
+            "#,
+        )?;
+        table.extend(toml::from_str::<toml::value::Table>(extra)?);
+        toml::Value::Table(table).try_into()
+    }
+
+    #[test]
+    fn config_load_without_optional_fields() {
+        let config = load_config_with("").unwrap();
+        assert_eq!(config.is_default_config(), true);
+    }
+
+    #[test]
+    fn config_footer_pattern() {
+        let config = load_config_with(r#"footer_pattern = '^(Fixes|Closes) (#\d+)$'"#).unwrap();
+        let footer_pattern = config.footer_pattern.as_ref().unwrap();
+        assert!(footer_pattern.regex().is_match("Text\nFixes #123"));
+        let saved: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(saved.footer_pattern, config.footer_pattern);
+
+        let error = load_config_with("footer_pattern = '('").unwrap_err();
+        assert!(error.to_string().contains("unclosed group"));
+        let error = load_config_with(r#"footer_pattern = '^Fixes (#\d+)$'"#).unwrap_err();
+        assert!(error.to_string().contains("needs a capture group"));
+    }
+
+    #[test]
+    fn config_category_delimiter_pairs() {
+        let config = load_config_with(r#"category_delimiters = [["[", "]"], ["(", ")"]]"#).unwrap();
+        assert_eq!(
+            config.category_delimiters,
+            vec![
+                ("[".to_owned(), "]".to_owned()),
+                ("(".to_owned(), ")".to_owned())
+            ]
+        );
+        let saved: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(saved.category_delimiters, config.category_delimiters);
+
+        let error = load_config_with("category_delimiters = []").unwrap_err();
+        assert!(error.to_string().contains("at least one pair"));
+        for delimiters in &[r#"["", "]"]"#, r#"[["[", "]"], ["(", ""]]"#] {
+            let error = load_config_with(&format!("category_delimiters = {}", delimiters));
+            assert!(error
+                .unwrap_err()
+                .to_string()
+                .contains("invalid category delimiters"));
+        }
+    }
+
+    #[test]
+    fn config_colors() {
+        let config = load_config_with(
+            r#"
+            [colors]
+            tag = "bright_cyan"
+            header = "magenta"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.colors.tag, term::color::BRIGHT_CYAN);
+        assert_eq!(config.colors.date, term::color::YELLOW);
+        assert_eq!(config.colors.header, term::color::MAGENTA);
+        assert!(toml::to_string(&config)
+            .unwrap()
+            .contains("tag = \"bright_cyan\""));
+
+        let error = load_config_with(
+            r#"
+            [colors]
+            category = "purple"
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown color 'purple'"));
+    }
+
+    #[test]
+    fn config_timezone() {
+        let toml = |timezone: &str| format!("timezone = \"{}\"", timezone);
+        for (timezone, expected) in &[
+            ("UTC", Timezone::Utc),
+            ("local", Timezone::Local),
+            ("+02:00", Timezone::Fixed(FixedOffset::east(2 * 3600))),
+            (
+                "-0530",
+                Timezone::Fixed(FixedOffset::west(5 * 3600 + 30 * 60)),
+            ),
+            ("+14", Timezone::Fixed(FixedOffset::east(14 * 3600))),
+        ] {
+            let config = load_config_with(&toml(timezone)).unwrap();
+            assert_eq!(config.timezone, *expected);
+        }
+        for timezone in &["Europe/Berlin", "+2", "+02:60", "02:00"] {
+            let error = load_config_with(&toml(timezone)).unwrap_err();
+            assert!(error.to_string().contains("invalid timezone"));
+        }
+        let config = load_config_with(&toml("-05:30")).unwrap();
+        assert!(toml::to_string(&config)
+            .unwrap()
+            .contains("timezone = \"-05:30\""));
+
+        let seconds = Utc.ymd(2020, 1, 2).and_hms(2, 0, 0).timestamp();
+        let mut config = Config::new();
+        assert_eq!(config.date_of(seconds), Utc.ymd(2020, 1, 2));
+        config.timezone = Timezone::parse("-05:30").unwrap();
+        assert_eq!(config.date_of(seconds), Utc.ymd(2020, 1, 1));
+    }
+
+    #[test]
+    fn config_severity() {
+        let mut config = load_config_with(
+            r#"
+            [verify_severity]
+            imperative_mood = "error"
+            unknown_tag = "off"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_severity(ProblemKind::ImperativeMood),
+            Severity::Error
+        );
+        assert_eq!(config.get_severity(ProblemKind::UnknownTag), Severity::Off);
+        assert_eq!(config.get_severity(ProblemKind::Spelling), Severity::Warn);
+        config.spellcheck_strict = true;
+        assert_eq!(config.get_severity(ProblemKind::Spelling), Severity::Error);
+        assert!(toml::to_string(&config).is_ok());
+    }
+
+    #[test]
+    fn config_bullet_char() {
+        let toml = toml::to_string(&Config::new()).unwrap();
+        let config: Config =
+            toml::from_str(&toml.replace("bullet_char = \"-\"", "bullet_char = \"*\"")).unwrap();
+        assert_eq!(config.bullet_char, "*");
+        for bullet_char in &["", "**", "x"] {
+            let invalid = format!("bullet_char = \"{}\"", bullet_char);
+            assert!(
+                toml::from_str::<Config>(&toml.replace("bullet_char = \"-\"", &invalid)).is_err()
+            );
+        }
+    }
+
     #[test]
     fn config_load_err() {
         load_and_print_failure("/dev/null");
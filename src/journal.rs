@@ -0,0 +1,4502 @@
+//! The git repository based changelog generation and commit message
+//! verification, which requires the `git` feature.
+
+use crate::{
+    config::{resolve_template, BranchTemplate, Config},
+    output::Output,
+    parser::{self, ParsedCommit, ParsedTag, Parser, Print, Tags},
+    verify::{Severity, Verifier, VerifyReport},
+    TEMPLATE_ENV,
+};
+use chrono::{offset::Utc, Date, Datelike, TimeZone};
+use failure::{bail, format_err, Error};
+use git2::{
+    BranchType, Commit, DiffOptions, ObjectType, Oid, Pathspec, PathspecFlags, Repository, Revwalk,
+};
+use lazy_static::lazy_static;
+use log::{info, warn, LevelFilter};
+use rayon::prelude::*;
+use regex::Regex;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashSet},
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, prelude::*},
+    iter,
+    path::{Path, PathBuf},
+};
+use toml::{map::Map, Value};
+
+#[cfg(feature = "github")]
+mod github;
+
+/// The summary prefixes of the commits which are meant to be autosquashed.
+const FIXUP_PREFIXES: [&str; 2] = ["fixup! ", "squash! "];
+
+/// The maximum size of the table to compute the diff of the changelog lines.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// The git hooks which can be installed via `GitJournal::install_hook`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    /// The `commit-msg` hook, which verifies the commit message
+    CommitMsg,
+
+    /// The `prepare-commit-msg` hook, which prepares the commit message
+    PrepareCommitMsg,
+}
+
+/// The events of `GitJournal::parse_log_streaming`, where the commits of a
+/// tag are emitted between its start and end.
+// The events are passed on right away, so boxing the commits is not worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseEvent {
+    /// The start of a tag, or "Unreleased" for commits without any tag
+    TagStart {
+        /// The name of the tag
+        name: String,
+
+        /// The date of the tag
+        date: Date<Utc>,
+    },
+
+    /// A parsed commit of the current tag
+    Commit(ParsedCommit),
+
+    /// The end of the current tag
+    TagEnd,
+}
+
+impl HookKind {
+    /// The file name of the hook within the hooks directory.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookKind::CommitMsg => "commit-msg",
+            HookKind::PrepareCommitMsg => "prepare-commit-msg",
+        }
+    }
+
+    /// The command which will be added to the hook.
+    fn command(self) -> &'static str {
+        match self {
+            HookKind::CommitMsg => "git journal v $1\n",
+            HookKind::PrepareCommitMsg => "git journal p $1 $2\n",
+        }
+    }
+}
+
+/// The options of the log parsing, which are built like
+/// `ParseOptions::new().max_tags_count(2).skip_unreleased(true)`. By default
+/// the parsing stops after the first tag and skips the tags containing `rc`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    tag_skip_pattern: String,
+    only_tags_pattern: Option<String>,
+    max_tags_count: u32,
+    all: bool,
+    skip_unreleased: bool,
+    ignore_tags: Option<Vec<String>>,
+    path_spec: Vec<String>,
+    limit: Option<usize>,
+}
+
+impl ParseOptions {
+    /// Constructs new `ParseOptions` with the default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tag_skip_pattern: "rc".to_owned(),
+            only_tags_pattern: None,
+            max_tags_count: 1,
+            all: false,
+            skip_unreleased: false,
+            ignore_tags: None,
+            path_spec: vec![],
+            limit: None,
+        }
+    }
+
+    /// A regular expression of the git tags which are no release boundaries,
+    /// where an invalid one is matched as substring.
+    #[must_use]
+    pub fn tag_skip_pattern(mut self, pattern: &str) -> Self {
+        self.tag_skip_pattern = pattern.to_owned();
+        self
+    }
+
+    /// A regular expression of the only git tags which are release
+    /// boundaries, where the skip pattern still takes precedence.
+    #[must_use]
+    pub fn only_tags_pattern(mut self, pattern: &str) -> Self {
+        self.only_tags_pattern = Some(pattern.to_owned());
+        self
+    }
+
+    /// The number of tags until the parsing of a single revision stops.
+    #[must_use]
+    pub fn max_tags_count(mut self, max_tags_count: u32) -> Self {
+        self.max_tags_count = max_tags_count;
+        self
+    }
+
+    /// Parses the whole history of a single revision. This resets the
+    /// `max_tags_count` to 0, since any other tags count contradicts it.
+    #[must_use]
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        if all {
+            self.max_tags_count = 0;
+        }
+        self
+    }
+
+    /// Skips the commits which do not belong to any release.
+    #[must_use]
+    pub fn skip_unreleased(mut self, skip_unreleased: bool) -> Self {
+        self.skip_unreleased = skip_unreleased;
+        self
+    }
+
+    /// Keeps only the commits without any of the given tags.
+    #[must_use]
+    pub fn ignore_tags(mut self, tags: &[&str]) -> Self {
+        self.ignore_tags = Some(tags.iter().map(|&tag| tag.to_owned()).collect());
+        self
+    }
+
+    /// Keeps only the commits which change any of the given paths.
+    #[must_use]
+    pub fn path_spec(mut self, path_spec: &[&str]) -> Self {
+        self.path_spec = path_spec.iter().map(|&spec| spec.to_owned()).collect();
+        self
+    }
+
+    /// Stops the parsing after the given number of commits, where the tags
+    /// only assign the commits to their release and do not stop the parsing.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The main structure of git-journal.
+pub struct GitJournal {
+    /// The configuration structure
+    pub config: Config,
+    parser: Parser,
+    path: PathBuf,
+    tags: Vec<(Oid, String, Option<i64>)>,
+    progress: Option<Box<dyn Fn(usize, usize) + Send + Sync>>,
+}
+
+impl GitJournal {
+    /// Constructs a new `GitJournal`. Searches upwards if the given path does
+    /// not contain the `.git` directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// When not providing a path with a valid git repository ('.git' folder or
+    /// the initial parsing of the git tags failed.
+    pub fn new(path: &str) -> Result<Self, Error> {
+        // Search upwards for the .git directory
+        let mut path_buf = if path == "." {
+            env::current_dir()?
+        } else {
+            PathBuf::from(path)
+        };
+        'git_search: loop {
+            for dir in fs::read_dir(&path_buf)? {
+                let dir_path = dir?.path();
+                if dir_path.ends_with(".git") {
+                    break 'git_search;
+                }
+            }
+            if !path_buf.pop() {
+                break;
+            }
+        }
+
+        // Open the repository
+        let repo = Repository::open(&path_buf)?;
+
+        // Get all available tags in some vector of tuples
+        let mut new_tags = vec![];
+        for name in repo.tag_names(None)?.iter() {
+            let name = name.ok_or_else(|| git2::Error::from_str("Could not receive tag name"))?;
+            let obj = repo.revparse_single(name)?;
+            if let Ok(tag) = obj.into_tag() {
+                let tag_name = tag
+                    .name()
+                    .ok_or_else(|| git2::Error::from_str("Could not parse tag name"))?
+                    .to_owned();
+                let tagger_time = tag.tagger().map(|tagger| tagger.when().seconds());
+                new_tags.push((tag.target_id(), tag_name, tagger_time));
+            }
+        }
+
+        // Search for config in path and load
+        let mut new_config = Config::new();
+        let load_error = new_config.load(path).err();
+
+        // Git decides about the comment character on its own for "auto"
+        if let Ok(comment_char) = repo.config()?.get_string("core.commentChar") {
+            if !comment_char.is_empty() && comment_char != "auto" {
+                new_config.comment_char = comment_char;
+            }
+        }
+
+        // Setup the logger if not already set
+        if new_config.enable_debug {
+            if new_config.colored_output {
+                if mowl::init_with_level(LevelFilter::Info).is_err() {
+                    warn!("Logger already set.");
+                };
+            } else if mowl::init_with_level_and_without_colors(LevelFilter::Info).is_err() {
+                warn!("Logger already set.");
+            };
+        }
+        if let Some(e) = load_error {
+            warn!("Can't load configuration file, using default one: {}", e);
+        }
+
+        // Create a new parser with empty results
+        let new_parser = Parser::new(new_config.clone());
+
+        // Return the git journal object
+        let mut journal = Self {
+            config: new_config,
+            parser: new_parser,
+            path: path_buf,
+            tags: new_tags,
+            progress: None,
+        };
+        journal.use_template_categories();
+        Ok(journal)
+    }
+
+    /// Replaces the configuration, which applies to the parsing and printing
+    /// of the journal as well. The categories are read from the default
+    /// template if `categories_from_template` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// let mut config = journal.config.clone();
+    /// config.title = Some("Changelog".to_owned());
+    /// journal.set_config(config);
+    /// ```
+    pub fn set_config(&mut self, config: Config) {
+        self.parser.set_config(config.clone());
+        self.config = config;
+        self.use_template_categories();
+    }
+
+    /// Replaces the configured categories by the tags of the default template
+    /// if `categories_from_template` is set, where the configured ones are kept
+    /// if the template is not readable.
+    fn use_template_categories(&mut self) {
+        if !self.config.categories_from_template {
+            return;
+        }
+        match self.template_categories() {
+            Ok(categories) => {
+                let mut parser_config = self.parser.config.clone();
+                parser_config.categories = categories.clone();
+                self.parser.set_config(parser_config);
+                self.config.categories = categories;
+            }
+            Err(e) => warn!("Can't read the categories from the default template: {}", e),
+        }
+    }
+
+    fn template_categories(&self) -> Result<Vec<String>, Error> {
+        let path_buf = self
+            .default_template()
+            .ok_or_else(|| format_err!("No default template configured."))?;
+        let toml = toml::from_str(&fs::read_to_string(path_buf)?)?;
+
+        // The default tag collects the uncategorized entries only
+        let mut categories = vec![];
+        for tag in self.parser.get_tags_from_toml(&toml, vec![]) {
+            if tag != parser::TOML_DEFAULT_KEY && !categories.contains(&tag) {
+                categories.push(tag);
+            }
+        }
+        if categories.is_empty() {
+            bail!("The default template does not contain any tags.");
+        }
+        Ok(categories)
+    }
+
+    /// Sets a callback which reports the progress of the revision walk during
+    /// the log parsing. It is called with the number of walked commits and
+    /// the total number of commits within the revision ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.set_progress(|current, total| eprint!("\r{}/{}", current, total));
+    /// ```
+    pub fn set_progress<F>(&mut self, progress: F)
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+    }
+
+    /// Does the setup on the target git repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal.setup().expect("Setup error");
+    /// ```
+    ///
+    /// Creates a `.gitjournal` file with the default values inside the given
+    /// path, which looks like:
+    ///
+    /// ```toml
+    /// # Specifies the available categories for the commit message, allowed regular expressions.
+    /// categories = ["Added", "Changed", "Fixed", "Improved", "Removed"]
+    ///
+    /// # Set the pairs of characters where the categories are wrapped in, e.g. `[["[", "]"],
+    /// # ["(", ")"]]`. The first pair will be used for the output.
+    /// category_delimiters = [["[", "]"]]
+    ///
+    /// # The separator of a scope within the category delimiters like `[Fixed:parser]`, where an
+    /// # empty one disables the scope parsing
+    /// category_scope_separator = ":"
+    ///
+    /// # Set to false if the output should not be colored
+    /// colored_output = true
+    ///
+    /// # Use colors within the terminal "always", "never" or "auto", which detects if the output is
+    /// # a TTY (`--color`)
+    /// color = "auto"
+    ///
+    /// # The commit message style: "default" for RFC0001 or "conventional" for `type(scope): text`
+    /// # summaries, where the categories are used as types
+    /// commit_style = "default"
+    ///
+    /// # Scopes which will be listed within the prepared conventional commit message template
+    /// known_scopes = []
+    ///
+    /// # Specifies the default template. Will be used for tag validation and printing. Can be
+    /// # removed from the configuration file as well, where the `GIT_JOURNAL_TEMPLATE` environment
+    /// # variable will be used as a fallback.
+    /// default_template = "CHANGELOG.toml"
+    ///
+    /// # Use the tags of the default template as categories instead of the configured ones
+    /// categories_from_template = false
+    ///
+    /// # Warn about the tags of the parsed commits which are not part of the default template
+    /// warn_unmapped_tags = false
+    ///
+    /// # Categories whose commits need a non empty body, e.g. "Removed"
+    /// require_body_for = []
+    ///
+    /// # Set to false to allow commit summaries without a category, which can be inferred from the
+    /// # changed paths afterwards
+    /// require_category = true
+    ///
+    /// # Use the first line of commit messages with an unparsable summary as summary without a
+    /// # category instead of failing, which makes `verify` accept these messages as well
+    /// lenient_summary = false
+    ///
+    /// # Show or hide the debug messages like `[OKAY] ...` or `[INFO] ...`
+    /// enable_debug = true
+    ///
+    /// # Strip simple inline markdown from the commit text on terminal output
+    /// escape_terminal_markdown = false
+    ///
+    /// # Render markdown links on terminal output: "off", "url" for `text (url)` or "text"
+    /// render_markdown_links = "off"
+    ///
+    /// # Escape the commit text within the markdown output, like a leading `#`,
+    /// # where only output files are escaped if unset
+    /// escape_markdown = true
+    ///
+    /// # Excluded tags in an array, e.g. "internal"
+    /// excluded_commit_tags = []
+    ///
+    /// # Excluded commits in an array, where abbreviated hashes are possible
+    /// exclude_commits = []
+    ///
+    /// # Include the commits which are meant to be autosquashed, like `fixup! Fixed a bug`
+    /// include_fixup_commits = false
+    ///
+    /// # Promote the categorized list items of squash merge commits to standalone entries, which
+    /// # allows asterisks as list markers as well
+    /// expand_squash_commits = false
+    ///
+    /// # Enable or disable the output and accumulation of commit footers.
+    /// enable_footers = false
+    ///
+    /// # The rendering of the footers: "plain" (default), "bold" for bold keys or "table" for a
+    /// # markdown table
+    /// footer_style = "plain"
+    ///
+    /// # A regex to detect the footer lines instead of the default `Key: value` ones, where the
+    /// # first capture group is the key and the second one the value. Can be removed from the
+    /// # configuration file as well.
+    /// footer_pattern = "^(Fixes|Closes) (#\\d+)$"
+    ///
+    /// # The key of a trailer which overrides the category of the summary, like `Category: Fixed`.
+    /// # Can be removed from the configuration file as well.
+    /// category_override_trailer = "Category"
+    ///
+    /// # The document flavor of the changelog: "markdown" (default), "gitlab", which wraps the
+    /// # template sections in collapsible `<details>` blocks, or "rst" for reStructuredText
+    /// document_format = "markdown"
+    ///
+    /// # Append the changelog to an existing output file instead of overwriting it (`--append`)
+    /// append_output = false
+    ///
+    /// # Collapse only template sections with more entries than this threshold when using the
+    /// # "gitlab" output format
+    /// collapse_threshold = 0
+    ///
+    /// # Highlight commits with a security footer in a separate section on top of every release
+    /// highlight_security = false
+    ///
+    /// # The footer key which marks security relevant commits
+    /// security_footer_key = "Security"
+    ///
+    /// # Show or hide the commit hash for every entry
+    /// show_commit_hash = false
+    ///
+    /// # The indentation of nested list items and paragraphs, which will be halved for the template
+    /// # based output
+    /// indent_width = 4
+    ///
+    /// # The bullet character of the list entries: "-", "*" or "+"
+    /// bullet_char = "-"
+    ///
+    /// # Wrap the summary text of the entries at this column, where the continuation lines are
+    /// # aligned under the text. Can be removed from the configuration file as well.
+    /// summary_wrap_width = 80
+    ///
+    /// # Show the git notes of the commits below the entries in detailed mode
+    /// show_notes = false
+    ///
+    /// # The git notes reference which will be used for `show_notes`
+    /// notes_ref = "refs/notes/commits"
+    ///
+    /// # Show the files changed by the commits below the entries in detailed mode
+    /// show_files_changed = false
+    ///
+    /// # The maximum number of files shown per commit, the remaining ones are summarized
+    /// max_files_changed = 10
+    ///
+    /// # Mark signed commits with a `✓ (unverified)` and unsigned ones with a
+    /// # ✗, where only the existence of a signature is checked
+    /// show_signature_status = false
+    ///
+    /// # Show the number of printed commits within the version header
+    /// show_commit_count = false
+    ///
+    /// # Print a table of contents with links to every version header on top of the markdown output
+    /// generate_toc = false
+    ///
+    /// # The title of the whole changelog, which is printed once on top of the output (`--title`).
+    /// # Can be removed from the configuration file as well.
+    /// title = "Changelog"
+    ///
+    /// # Render the tags of the template based output in parallel, except for the colored terminal
+    /// render_parallel = true
+    ///
+    /// # Show or hide the commit message prefix, e.g. JIRA-1234
+    /// show_prefix = false
+    ///
+    /// # Path to a newline separated word list, which will be used to spell check the commit
+    /// # summary during verification. Can be removed from the configuration file as well.
+    /// spellcheck_wordlist = "wordlist.txt"
+    ///
+    /// # Fail the verification on unknown words instead of only warning
+    /// spellcheck_strict = false
+    ///
+    /// # Warn if the summary text after the category does not start with a verb in imperative mood
+    /// imperative_mood_check = false
+    ///
+    /// # Word endings which indicate a non imperative mood
+    /// imperative_mood_suffixes = ["ed", "ing", "s"]
+    ///
+    /// # Words which are accepted even if they end with one of the suffixes
+    /// imperative_mood_exceptions = ["access", "address", "bring", "bypass", "discuss", "embed",
+    ///                               "express", "focus", "need", "pass", "process", "speed"]
+    ///
+    /// # Words or phrases which are not allowed within the commit message, e.g. "WIP". They match
+    /// # case insensitive on whole words only.
+    /// disallowed_phrases = []
+    ///
+    /// # Treat the disallowed phrases as regular expressions instead
+    /// disallowed_phrases_regex = false
+    ///
+    /// # The maximum number of lines of the commit message body, where comment lines are not
+    /// # counted. Can be removed from the configuration file as well.
+    /// max_body_lines = 20
+    ///
+    /// # The maximum number of characters of every commit message body line. Can be removed from
+    /// # the configuration file as well.
+    /// max_body_line_length = 72
+    ///
+    /// # Fix the trailing whitespace and a final period of the summary as well as the category
+    /// # casing of the summary and list items within the commit-msg hook before the verification
+    /// autofix = false
+    ///
+    /// # Sort the commits during the output by "date" (default) or "name", which sorts by the
+    /// # category and then by the summary text
+    /// sort_by = "date"
+    ///
+    /// # The direction of the "date" sorting: "descending" (default) or "ascending"
+    /// sort_direction = "descending"
+    ///
+    /// # The order of the commits within a tag: "newest" (default) first or "oldest" first, which
+    /// # reverses the sorted commits
+    /// commit_order = "newest"
+    ///
+    /// # Group the commits by "tag" (default), "month" or summary "prefix", where the git tags
+    /// # are ignored for the latter two
+    /// group_by = "tag"
+    ///
+    /// # The release name of multiple tags pointing to the same commit: "semver" (default) prefers
+    /// # the first semantic version like tag, whereas "merge" joins all of them
+    /// tag_preference = "semver"
+    ///
+    /// # The date of a release: "commit" (default) for the tagged commit or "tag" for the tagger date
+    /// release_date_source = "commit"
+    ///
+    /// # The timezone of the release dates: "utc" (default), "local" or a fixed offset like "+02:00"
+    /// timezone = "utc"
+    ///
+    /// # Skip the commits without any relation to a git tag by default (`--skip-unreleased`)
+    /// skip_unreleased = false
+    ///
+    /// # Parse the whole history by default instead of stopping at the tags (`--all`)
+    /// parse_all = false
+    ///
+    /// # Group the commits of the detailed output without a template in sections per category
+    /// group_by_category = false
+    ///
+    /// # Categories where the commit body is printed within the short output as well
+    /// always_expand_categories = []
+    ///
+    /// # Print the category of consecutive list items only once as a sub-header
+    /// collapse_repeated_list_categories = false
+    ///
+    /// # Append a single line paragraph right after the summary to the summary line
+    /// treat_first_paragraph_as_subtitle = false
+    ///
+    /// # The separator between the summary and its subtitle
+    /// subtitle_separator = " — "
+    ///
+    /// # Commit message template prefix which will be added during commit preparation.
+    /// template_prefix = "JIRA-1234"
+    ///
+    /// # Show the emoji of the `category_emoji` mapping in front of the categories within the output
+    /// show_category_emoji = false
+    ///
+    /// # The emoji or shortcodes of the categories
+    /// [category_emoji]
+    /// Added = "✨"
+    /// Fixed = ":bug:"
+    ///
+    /// # The displayed names of the categories within the output
+    /// [category_headings]
+    /// Added = "New Features"
+    /// Fixed = "Bug Fixes"
+    ///
+    /// # The displayed labels of the footer keys within the output
+    /// [footer_labels]
+    /// Reviewed-by = "Reviewed by"
+    ///
+    /// # Categories of commits without a category, which will be used if all changed paths of the
+    /// # commit match the path glob
+    /// [infer_category_from_paths]
+    /// "tests/*" = "Tested"
+    ///
+    /// # Commit message templates of the branches matching the glob, which replace the
+    /// # `template_prefix` and `categories` during the commit preparation
+    /// [branch_templates."hotfix/*"]
+    /// template_prefix = "HOTFIX-1234"
+    /// categories = ["Fixed"]
+    ///
+    /// # The colors of the terminal output, which are "black", "red", "green", "yellow", "blue",
+    /// # "magenta", "cyan", "white" or their "bright_" variants like "bright_red"
+    /// [colors]
+    /// tag = "green"
+    /// date = "yellow"
+    /// category = "bright_blue"
+    /// text = "white"
+    /// header = "bright_red"
+    ///
+    /// # Overwrite the severity ("warn", "error" or "off") of the verification rules
+    /// # "parse", "unknown_tag", "missing_body", "spelling", "imperative_mood",
+    /// # "disallowed_phrase", "body_length" and "unknown_category"
+    /// [verify_severity]
+    /// imperative_mood = "error"
+    /// ```
+    ///
+    /// It also creates a symlinks for the commit message validation and
+    /// preparation hook inside the given git repository.
+    ///
+    /// # Errors
+    /// - When the writing of the default configuration fails.
+    /// - When installation of the commit message (preparation) hook fails.
+    pub fn setup(&self) -> Result<(), Error> {
+        // Save the default config
+        let output_file = Config::new().save_default_config(self.path_as_str())?;
+        info!("Defaults written to '{}' file.", output_file);
+
+        // Install commit message hook
+        self.install_hook(HookKind::CommitMsg)?;
+
+        // Install the prepare commit message hook
+        self.install_hook(HookKind::PrepareCommitMsg)?;
+
+        Ok(())
+    }
+
+    /// Installs a single git hook, which is useful if not everything of the
+    /// `setup` is wanted. The command will be appended to an already existing
+    /// hook.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, HookKind};
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .install_hook(HookKind::CommitMsg)
+    ///     .expect("Hook installation failed");
+    /// ```
+    ///
+    /// # Errors
+    /// When the hook could not be written.
+    pub fn install_hook(&self, kind: HookKind) -> Result<(), Error> {
+        self.install_git_hook(kind.name(), kind.command())
+    }
+
+    fn path_as_str(&self) -> &str {
+        self.path.to_str().unwrap_or("")
+    }
+
+    fn install_git_hook(&self, name: &str, content: &str) -> Result<(), Error> {
+        let mut hook_path = self.hooks_path()?;
+        fs::create_dir_all(&hook_path)?;
+        hook_path.push(name);
+        let mut hook_file: File;
+
+        if hook_path.exists() {
+            warn!(
+                "There is already a hook available in '{}'. Please verifiy \
+                 the hook by hand after the installation.",
+                hook_path.display()
+            );
+            hook_file = OpenOptions::new()
+                .read(true)
+                .append(true)
+                .open(&hook_path)?;
+            let mut hook_content = String::new();
+            hook_file.read_to_string(&mut hook_content)?;
+            if hook_content.contains(content) {
+                info!("Hook already installed, nothing changed in existing hook.");
+                return Ok(());
+            }
+        } else {
+            hook_file = File::create(&hook_path)?;
+            hook_file.write_all(b"#!/usr/bin/env sh\n")?;
+        }
+        hook_file.write_all(content.as_bytes())?;
+        Self::chmod(&hook_path, 0o755)?;
+
+        info!("Git hook installed to '{}'.", hook_path.display());
+        Ok(())
+    }
+
+    fn hooks_path(&self) -> Result<PathBuf, Error> {
+        let repo = Repository::open(&self.path)?;
+
+        // Relative hook paths are relative to the working directory
+        match repo.config()?.get_path("core.hooksPath") {
+            Ok(hooks_path) => Ok(self.path.join(hooks_path)),
+            Err(_) => Ok(repo.path().join("hooks")),
+        }
+    }
+
+    #[cfg(unix)]
+    fn chmod(path: &Path, perms: u32) -> Result<(), Error> {
+        use std::os::unix::prelude::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(perms))?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn chmod(_path: &Path, _perms: u32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Prepare a commit message before the user edits it. This includes also a
+    /// verification of the commit message, e.g. for amended commits. The
+    /// `commit_type` is the source of the commit message as provided to the
+    /// `prepare-commit-msg` hook, like "message", "merge", "squash" or
+    /// "commit". The template of the first `branch_templates` glob matching the
+    /// current branch replaces the default prefix and categories.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .prepare("./tests/commit_messages/success_1", None)
+    ///     .expect("Commit message preparation error");
+    /// ```
+    ///
+    /// # Errors
+    /// When the path is not available or writing the commit message fails.
+    pub fn prepare(&self, path: &str, commit_type: Option<&str>) -> Result<(), Error> {
+        // If the message is not valid, assume a new commit and provide the
+        // template.
+        if let Err(error) = self.verify(path) {
+            match commit_type {
+                // But if the message is provided via the cli with `-m`, then
+                // abort since the user can not edit this message any more.
+                Some("message") => return Err(error),
+
+                // Merge and squash messages are generated by git and should be
+                // kept as they are.
+                Some("merge") | Some("squash") => return Ok(()),
+                _ => {}
+            }
+
+            // Read the file contents to get the actual commit message string
+            let mut read_file = File::open(path)?;
+            let mut commit_message = String::new();
+            read_file.read_to_string(&mut commit_message)?;
+
+            // Write the new generated content to the file
+            let mut file = OpenOptions::new().write(true).open(path)?;
+            // Keep empty lines as empty comments to retain the paragraph breaks
+            let comment = &self.config.comment_char;
+            let mut old_msg_vec = commit_message
+                .trim()
+                .lines()
+                .map(|line| match line {
+                    "" => comment.to_string(),
+                    l if self.config.is_comment_line(l) => l.to_string(),
+                    l => format!("{} {}", comment, l),
+                })
+                .collect::<Vec<_>>();
+            if !old_msg_vec.is_empty() {
+                old_msg_vec.insert(0, format!("{} The provided commit message:", comment));
+            }
+            let branch_template = self.branch_template()?;
+            let mut prefix = branch_template
+                .and_then(|t| t.template_prefix.clone())
+                .unwrap_or_else(|| self.config.template_prefix.clone());
+            if !prefix.is_empty() {
+                prefix.push(' ');
+            }
+            let categories = branch_template
+                .and_then(|t| t.categories.as_ref())
+                .filter(|c| !c.is_empty())
+                .unwrap_or(&self.config.categories);
+            let summary = if self.config.commit_style == "conventional" {
+                categories[0].clone() + "(<scope>): ..."
+            } else {
+                categories[0].clone() + " ..."
+            };
+            let mut scopes = String::new();
+            if self.config.commit_style == "conventional" && !self.config.known_scopes.is_empty() {
+                scopes = format!(
+                    "{0} Available scopes:\n{0} - {1}\n\n",
+                    comment,
+                    self.config.known_scopes.join(&format!("\n{} - ", comment))
+                );
+            }
+            let new_content = prefix
+                + &summary
+                + &format!(
+                    "\n\n{0} Add a more detailed description if needed\n\n{0} - {1}\n\n",
+                    comment,
+                    categories.join(&format!("\n{} - ", comment))
+                )
+                + &scopes
+                + &old_msg_vec.join("\n");
+            file.write_all(new_content.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the commit message template of the first `branch_templates`
+    /// glob which matches the currently checked out branch, if any.
+    fn branch_template(&self) -> Result<Option<&BranchTemplate>, Error> {
+        if self.config.branch_templates.is_empty() {
+            return Ok(None);
+        }
+
+        // The symbolic target is available for unborn branches as well
+        let repo = Repository::open(&self.path)?;
+        let head = repo.find_reference("HEAD")?;
+        let branch = match head
+            .symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+        {
+            Some(branch) => branch.to_owned(),
+            None => return Ok(None),
+        };
+
+        for (glob, template) in &self.config.branch_templates {
+            let pathspec = Pathspec::new(iter::once(glob))?;
+            if pathspec.matches_path(Path::new(&branch), PathspecFlags::DEFAULT) {
+                info!("Using the commit message template of branch '{}'", branch);
+                return Ok(Some(template));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fixes minor issues of a given commit message in place and verifies it
+    /// afterwards. Fixable issues are trailing whitespace and a final period of
+    /// the summary as well as a wrong casing of the category within the summary
+    /// and the list items. The body text is kept untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .autofix("tests/commit_messages/success_1")
+    ///     .expect("Commit message verification error");
+    /// ```
+    ///
+    /// # Errors
+    /// When the commit message is still not valid after fixing it or the
+    /// reading or writing of the given file failed.
+    pub fn autofix(&self, path: &str) -> Result<(), Error> {
+        let commit_message = fs::read_to_string(path)?;
+        let mut fixes = vec![];
+
+        lazy_static! {
+            static ref RE_LIST_ITEM: Regex = Regex::new(r"^\s*[-*+]\s").unwrap();
+        }
+        let categories = self
+            .config
+            .categories
+            .iter()
+            .filter_map(|category| {
+                Some((
+                    category.as_str(),
+                    Regex::new(&format!("^(?:{})$", category)).ok()?,
+                    Regex::new(&format!("(?i)^(?:{})$", category)).ok()?,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut lines = commit_message
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<String>>();
+        if let Some(summary) = lines.first_mut() {
+            let trimmed_len = summary.trim_end().len();
+            if trimmed_len < summary.len() {
+                summary.truncate(trimmed_len);
+                fixes.push("trailing whitespace");
+            }
+            if summary.ends_with('.') && !summary.ends_with("..") {
+                summary.pop();
+                fixes.push("final period");
+            }
+        }
+
+        // Only the category of the summary and the list items may be fixed
+        for (index, line) in lines.iter_mut().enumerate() {
+            if (index == 0 || RE_LIST_ITEM.is_match(line)) && !self.config.is_comment_line(line) {
+                if let Some(fixed_line) = Self::fix_category_casing(line, &categories) {
+                    *line = fixed_line;
+                    if !fixes.contains(&"category casing") {
+                        fixes.push("category casing");
+                    }
+                }
+            }
+        }
+
+        if !fixes.is_empty() {
+            let mut fixed_message = lines.join("\n");
+            if commit_message.ends_with('\n') {
+                fixed_message.push('\n');
+            }
+            fs::write(path, fixed_message)?;
+            info!("Fixed the commit message: {}.", fixes.join(", "));
+        }
+        self.verify(path)
+    }
+
+    /// Returns the line with the configured casing of the category, if the
+    /// category matches only case insensitive. The categories are given
+    /// together with their exact and case insensitive regular expression.
+    fn fix_category_casing(line: &str, categories: &[(&str, Regex, Regex)]) -> Option<String> {
+        lazy_static! {
+            static ref RE_CATEGORY_WORD: Regex =
+                Regex::new(r"^(\s*[-*+]\s+)?([A-Za-z]+-\d+\s+)?\W?([A-Za-z]+)").unwrap();
+        }
+        let word = RE_CATEGORY_WORD.captures(line)?.get(3)?;
+        for &(category, ref regex, ref case_insensitive) in categories {
+            if regex.is_match(word.as_str()) {
+                return None;
+            }
+            if case_insensitive.is_match(word.as_str()) {
+                // Categories given as regular expression get capitalized
+                let fixed = if regex::escape(category) == *category {
+                    category.to_owned()
+                } else {
+                    let lowercase = word.as_str().to_lowercase();
+                    let mut chars = lowercase.chars();
+                    chars.next()?.to_uppercase().chain(chars).collect()
+                };
+                if regex.is_match(&fixed) {
+                    return Some(format!(
+                        "{}{}{}",
+                        &line[..word.start()],
+                        fixed,
+                        &line[word.end()..]
+                    ));
+                }
+            }
+        }
+        None
+    }
+
+    /// Verify a given commit message against the parsing rules of
+    /// [RFC0001](https://github.com/saschagrunert/git-journal/blob/master/rfc/0001-commit-msg.md)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .verify("tests/commit_messages/success_1")
+    ///     .expect("Commit message verification error");
+    /// ```
+    ///
+    /// # Errors
+    /// When the commit message is not valid due to RFC0001 or opening of the
+    /// given file failed.
+    pub fn verify(&self, path: &str) -> Result<(), Error> {
+        let report = self.verify_report(path)?;
+        for warning in report.problems_with(Severity::Warn) {
+            warn!("{}", warning);
+        }
+        if !report.valid {
+            bail!(
+                "{}",
+                report
+                    .problems_with(Severity::Error)
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Verify a given commit message like `verify`, but collect all found
+    /// problems in a structured report instead of failing on the first one.
+    /// The report is only invalid if an error level problem exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// let report = journal
+    ///     .verify_report("tests/commit_messages/success_1")
+    ///     .expect("Commit message verification error");
+    /// assert!(report.valid);
+    /// ```
+    ///
+    /// # Errors
+    /// When opening of the given file or the default template failed, or a
+    /// disallowed phrase is not a valid regular expression.
+    pub fn verify_report(&self, path: &str) -> Result<VerifyReport, Error> {
+        // Open the file and read to string
+        let mut file = File::open(path)?;
+        let mut commit_message = String::new();
+        file.read_to_string(&mut commit_message)?;
+        Ok(Verifier::new(&self.config, &self.path)?.verify_message(&commit_message))
+    }
+
+    /// Verifies all commits since the latest release like `verify`, which can
+    /// be used as a gate before tagging a new release. Merge commits are
+    /// skipped, because their messages are generated by git.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// if let Err(e) = journal.verify_unreleased() {
+    ///     println!("Not ready for a release: {}", e);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When at least one commit message is not valid, where all of them will
+    /// be listed, or the revision walk failed.
+    pub fn verify_unreleased(&self) -> Result<(), Error> {
+        let repo = Repository::open(&self.path)?;
+        let verifier = Verifier::new(&self.config, &self.path)?;
+        let mut invalid_commits = vec![];
+        for oid in walk_revision_range(&repo, &self.unreleased_range())? {
+            let commit = repo.find_commit(oid)?;
+            if commit.parent_count() > 1 || self.is_excluded_commit(oid) {
+                continue;
+            }
+            let report = verifier.verify_message(&String::from_utf8_lossy(commit.message_bytes()));
+            for warning in report.problems_with(Severity::Warn) {
+                warn!("Commit {}: {}", oid, warning);
+            }
+            if !report.valid {
+                invalid_commits.push(format!(
+                    "{} ({})",
+                    oid,
+                    report
+                        .problems_with(Severity::Error)
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        if !invalid_commits.is_empty() {
+            bail!(
+                "Invalid commits since the latest release: {}",
+                invalid_commits.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the sorted tags of the parsed commits which are not part of the
+    /// default template, where excluded tags are left out. Nothing is returned
+    /// if no default template is configured.
+    fn unmapped_tags(&self) -> Result<Vec<String>, Error> {
+        let path_buf = match self.default_template() {
+            Some(path_buf) => path_buf,
+            None => return Ok(vec![]),
+        };
+        let toml = toml::from_str(&fs::read_to_string(path_buf)?)?;
+        let toml_tags = self.parser.get_tags_from_toml(&toml, vec![]);
+
+        let mut unmapped_tags = self
+            .parser
+            .result
+            .iter()
+            .flat_map(|tag| &tag.commits)
+            .flat_map(|commit| commit.get_tags(vec![]))
+            .filter(|tag| {
+                !toml_tags.contains(tag) && !self.config.excluded_commit_tags.contains(tag)
+            })
+            .collect::<Vec<_>>();
+        unmapped_tags.sort();
+        unmapped_tags.dedup();
+        Ok(unmapped_tags)
+    }
+
+    /// The path of the default template, which is the configured one or the
+    /// one of the `GIT_JOURNAL_TEMPLATE` environment variable as a fallback.
+    fn default_template(&self) -> Option<PathBuf> {
+        resolve_template(
+            &self.path,
+            self.config.default_template.as_deref(),
+            env::var_os(TEMPLATE_ENV),
+        )
+    }
+
+    /// Parses a revision range for a `GitJournal`. For a single revision the
+    /// parsing stops after the `max_tags_count` tags of the `options`, unless
+    /// all commits should be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// ```
+    ///
+    /// # Errors
+    /// When something during the parsing fails, for example if the revision
+    /// range is invalid.
+    pub fn parse_log(
+        &mut self,
+        revision_ranges: &[&str],
+        options: &ParseOptions,
+    ) -> Result<(), Error> {
+        self.parse_log_with_filter(revision_ranges, options, |_| true)
+    }
+
+    /// Parses a revision range for a `GitJournal`, but skips all commits where
+    /// the `filter` returns false. The filter is called sequentially during
+    /// the revision walk on the calling thread, so it does not have to be
+    /// thread safe. Only the parsing of the commit messages afterwards is done
+    /// in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log_with_filter(&["HEAD"], &ParseOptions::new(), |commit| {
+    ///     commit.author().name() != Some("bot")
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    /// When something during the parsing fails, for example if the revision
+    /// range is invalid or all commits are parsed with a `max_tags_count`
+    /// other than 0.
+    pub fn parse_log_with_filter<F>(
+        &mut self,
+        revision_ranges: &[&str],
+        options: &ParseOptions,
+        filter: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&Commit) -> bool,
+    {
+        let mut parsed_tags = vec![];
+        self.walk_log(revision_ranges, options, filter, |parsed_tag| {
+            parsed_tags.push(parsed_tag)
+        })?;
+        self.parser.result.extend(parsed_tags);
+        for warning in self.result_warnings()? {
+            warn!("{}", warning);
+        }
+        Ok(())
+    }
+
+    /// The warnings about the parsed result, which are emitted after parsing
+    /// the log.
+    fn result_warnings(&self) -> Result<Vec<String>, Error> {
+        let mut warnings = vec![];
+
+        // Typos within the tags would silently remove the commits from the output
+        if self.config.warn_unmapped_tags {
+            let unmapped_tags = self.unmapped_tags()?;
+            if !unmapped_tags.is_empty() {
+                warnings.push(format!(
+                    "These tags are not part of the default template and will not appear in \
+                     its output: '{}'.",
+                    unmapped_tags.join(", ")
+                ));
+            }
+        }
+
+        // An empty changelog looks like a failure without any explanation
+        if self.parser.result.is_empty() {
+            warnings.push(
+                "No commits matched the revision range and the filters, so the changelog will \
+                 be empty. Check the excluded tags and commits, the path specs and the tag \
+                 patterns."
+                    .to_owned(),
+            );
+        }
+        Ok(warnings)
+    }
+
+    /// Parses a revision range like `parse_log`, but emits the results as
+    /// events instead of collecting them within the journal. Every tag is
+    /// emitted as soon as all of its commits are parsed, which allows
+    /// consumers to render the releases incrementally while only the commits
+    /// of a single tag are kept in memory. The synthetic groups of `group_by`
+    /// need all commits and are therefore emitted at the end of the parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseEvent, ParseOptions};
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .parse_log_streaming(&["HEAD"], &ParseOptions::new(), |event| {
+    ///         if let ParseEvent::TagStart { name, .. } = event {
+    ///             println!("{}", name);
+    ///         }
+    ///     })
+    ///     .expect("Parsing failed");
+    /// ```
+    ///
+    /// # Errors
+    /// When something during the parsing fails, for example if the revision
+    /// range is invalid or all commits are parsed with a `max_tags_count`
+    /// other than 0.
+    pub fn parse_log_streaming<S>(
+        &self,
+        revision_ranges: &[&str],
+        options: &ParseOptions,
+        mut sink: S,
+    ) -> Result<(), Error>
+    where
+        S: FnMut(ParseEvent),
+    {
+        self.walk_log(
+            revision_ranges,
+            options,
+            |_| true,
+            |parsed_tag| {
+                sink(ParseEvent::TagStart {
+                    name: parsed_tag.name,
+                    date: parsed_tag.date,
+                });
+                for commit in parsed_tag.commits {
+                    sink(ParseEvent::Commit(commit));
+                }
+                sink(ParseEvent::TagEnd);
+            },
+        )
+    }
+
+    /// Walks the revision ranges and passes every parsed tag to the `sink` as
+    /// soon as all of its commits are parsed.
+    fn walk_log<F, S>(
+        &self,
+        revision_ranges: &[&str],
+        options: &ParseOptions,
+        filter: F,
+        mut sink: S,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&Commit) -> bool,
+        S: FnMut(ParsedTag),
+    {
+        if options.all && options.max_tags_count != 0 {
+            bail!(
+                "Parsing all commits contradicts a tags count of {}, use a tags count of 0 instead.",
+                options.max_tags_count
+            );
+        }
+        let repo = Repository::open(&self.path)?;
+
+        // A single revision range is walked lazily, since the walk usually
+        // stops at the first tag. Multiple ranges have to be ordered against
+        // each other and the progress needs the total up front. Every commit
+        // is marked if it starts another range.
+        type Oids<'a> = Box<dyn Iterator<Item = Result<(bool, Oid), git2::Error>> + 'a>;
+        let (oids, total): (Oids, _) = match revision_ranges {
+            [revision_range] if self.progress.is_none() => (
+                Box::new(revision_walk(&repo, revision_range)?.map(|oid| Ok((false, oid?)))),
+                0,
+            ),
+            _ => {
+                let ranges = collect_revision_ranges(&repo, revision_ranges)?;
+                let total = ranges.iter().map(Vec::len).sum();
+                let oids = ranges.into_iter().flat_map(|oids| {
+                    oids.into_iter()
+                        .enumerate()
+                        .map(|(index, oid)| Ok((index == 0, oid)))
+                });
+                (Box::new(oids), total)
+            }
+        };
+
+        // Compile the tag patterns once for the whole revwalk
+        let tag_skip_pattern = TagPattern::new(&options.tag_skip_pattern);
+        let only_tags_pattern = options.only_tags_pattern.as_deref().map(TagPattern::new);
+        let ignore_tags = options.ignore_tags.as_deref();
+
+        // Iterate over the git objects and collect them in a vector of tuples
+        let mut num_parsed_tags: u32 = 1;
+        let unreleased_str = "Unreleased";
+        let unreleased_tag = || ParsedTag {
+            name: unreleased_str.to_owned(),
+            date: self.config.date_of(Utc::now().timestamp()),
+            commits: vec![],
+            message_ids: vec![],
+        };
+        let mut current_tag = unreleased_tag();
+        let ignore_git_tags = self.config.group_by == "month" || self.config.group_by == "prefix";
+        let mut walked_commits = vec![];
+        let mut num_walked = 0;
+        'revloop: for (index, oid) in oids.enumerate() {
+            // Stop if enough commits are collected
+            if options.limit == Some(num_walked) {
+                break;
+            }
+            let (starts_range, oid) = oid?;
+
+            if let Some(ref progress) = self.progress {
+                progress(index + 1, total);
+            }
+
+            // The releases of another range do not continue the previous one
+            if starts_range && index > 0 {
+                if !walked_commits.is_empty() {
+                    self.finish_tag(
+                        current_tag.clone(),
+                        &mut walked_commits,
+                        ignore_tags,
+                        &mut sink,
+                    );
+                }
+                current_tag = unreleased_tag();
+            }
+
+            let commit = repo.find_commit(oid)?;
+
+            // Multiple tags pointing to the same commit result in a single release
+            let tag_names = self
+                .tags
+                .iter()
+                .filter(|tag| {
+                    !ignore_git_tags
+                        && tag.0 == oid
+                        && is_release_tag(&tag.1, &tag_skip_pattern, only_tags_pattern.as_ref())
+                })
+                .map(|tag| tag.1.as_str())
+                .collect::<Vec<_>>();
+            if !tag_names.is_empty() {
+                // Parsing entries of the last tag done
+                if !walked_commits.is_empty() {
+                    self.finish_tag(
+                        current_tag.clone(),
+                        &mut walked_commits,
+                        ignore_tags,
+                        &mut sink,
+                    );
+                }
+
+                // If a single revision is given stop at the first seen tag
+                if !options.all
+                    && options.limit.is_none()
+                    && index > 0
+                    && num_parsed_tags > options.max_tags_count
+                {
+                    break 'revloop;
+                }
+
+                // Format the tag and set as current
+                num_parsed_tags += 1;
+                current_tag = ParsedTag {
+                    name: select_tag_name(&tag_names, &self.config.tag_preference),
+                    date: self.release_date(&tag_names, &commit),
+                    commits: vec![],
+                    message_ids: vec![],
+                };
+            }
+
+            // Do not parse if we want to skip commits which do not belong to
+            // any release
+            if options.skip_unreleased && !ignore_git_tags && current_tag.name == unreleased_str {
+                continue;
+            }
+
+            if !options.path_spec.is_empty() && skip_commit(&repo, &commit, &options.path_spec)? {
+                continue;
+            }
+
+            if !filter(&commit) || self.is_excluded_commit(oid) || self.is_fixup_commit(&commit) {
+                continue;
+            }
+
+            // Add the commit to the parser work to be done, the `id`
+            // represents the index of all walked commits
+            current_tag.message_ids.push(num_walked);
+            num_walked += 1;
+            walked_commits.push(self.walk_commit(&repo, &commit)?);
+        }
+
+        // Add the last element as well if needed
+        if !walked_commits.is_empty() {
+            self.finish_tag(current_tag, &mut walked_commits, ignore_tags, &mut sink);
+        }
+
+        info!("Parsing done. Processed {} commit messages.", num_walked);
+        Ok(())
+    }
+
+    /// Parses the walked commits of a tag and passes the assembled tag to the
+    /// `sink`, where the synthetic groups of `group_by` replace the tag.
+    fn finish_tag<S>(
+        &self,
+        parsed_tag: ParsedTag,
+        walked_commits: &mut Vec<WalkedCommit>,
+        ignore_tags: Option<&[String]>,
+        sink: &mut S,
+    ) where
+        S: FnMut(ParsedTag),
+    {
+        self.parse_walked_commits(walked_commits, ignore_tags);
+
+        // The ids of the tag start at its first walked commit
+        let first_id = parsed_tag.message_ids.first().copied().unwrap_or_default();
+        let parsed_tags = match self.config.group_by.as_str() {
+            "month" => group_into_months(&[parsed_tag], walked_commits, first_id, &self.config),
+            "prefix" => group_into_prefixes(&[parsed_tag], walked_commits, first_id, &self.config),
+            _ => vec![parsed_tag],
+        };
+        for parsed_tag in parsed_tags {
+            if let Some(parsed_tag) = self.assemble_tag(parsed_tag, walked_commits, first_id) {
+                sink(parsed_tag);
+            }
+        }
+        walked_commits.clear();
+    }
+
+    /// The parsed tags of the latest `parse_log` call, where the newest tag
+    /// comes first. They can be serialized for external changelog tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// let json = serde_json::to_string(journal.parsed_tags()).unwrap();
+    /// ```
+    pub fn parsed_tags(&self) -> &[ParsedTag] {
+        &self.parser.result
+    }
+
+    /// The revision range of the commits since the latest release, like
+    /// `v1.2.0..HEAD`, or `HEAD` if there is no release at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// println!("Unreleased changes: {}", journal.unreleased_range());
+    /// ```
+    pub fn unreleased_range(&self) -> String {
+        match self.releases().first() {
+            Some((name, _)) => format!("{}..HEAD", name),
+            None => "HEAD".to_owned(),
+        }
+    }
+
+    /// Parses all commits since the latest release, which is the release with
+    /// the newest tagged commit. This equals parsing the range of
+    /// `unreleased_range` without any further filtering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_unreleased().expect("Parsing failed");
+    /// ```
+    ///
+    /// # Errors
+    /// When something during the parsing fails.
+    pub fn parse_unreleased(&mut self) -> Result<(), Error> {
+        let revision_range = self.unreleased_range();
+        self.parse_log(&[&revision_range], &ParseOptions::new().all(true))
+    }
+
+    /// The revision of the tip of a local or remote branch like `release-2.x`
+    /// or `origin/release-2.x`, which does not have to be checked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// if let Ok(revision) = journal.branch_range("master") {
+    ///     println!("Tip of master: {}", revision);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the branch does not exist.
+    pub fn branch_range(&self, branch: &str) -> Result<String, Error> {
+        let repo = Repository::open(&self.path)?;
+        let branch = repo
+            .find_branch(branch, BranchType::Local)
+            .or_else(|_| repo.find_branch(branch, BranchType::Remote))?;
+        let oid = branch.get().peel_to_commit()?.id();
+        Ok(oid.to_string())
+    }
+
+    /// Parses the whole history of a branch, which equals parsing the range of
+    /// `branch_range` without any further filtering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// if let Err(e) = journal.parse_log_branch("master") {
+    ///     println!("{}", e);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the branch does not exist or something during the parsing fails.
+    pub fn parse_log_branch(&mut self, branch: &str) -> Result<(), Error> {
+        let revision_range = self.branch_range(branch)?;
+        self.parse_log(&[&revision_range], &ParseOptions::new().all(true))
+    }
+
+    /// Lists the releases (git tags) together with the dates of their tagged
+    /// commits, without parsing any commit message. The newest release comes
+    /// first. Tags which do not point to a commit are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// for (name, date) in journal.releases() {
+    ///     println!("{} ({})", name, date);
+    /// }
+    /// ```
+    pub fn releases(&self) -> Vec<(String, Date<Utc>)> {
+        let repo = match Repository::open(&self.path) {
+            Ok(repo) => repo,
+            Err(e) => {
+                warn!("Unable to open the repository: {}", e);
+                return vec![];
+            }
+        };
+        let mut releases = self
+            .tags
+            .iter()
+            .filter_map(|(oid, name, _)| {
+                let commit = repo.find_commit(*oid).ok()?;
+                let date = self.release_date(&[name.as_str()], &commit);
+                Some((name.clone(), commit.time().seconds(), date))
+            })
+            .collect::<Vec<_>>();
+        releases.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        releases
+            .into_iter()
+            .map(|(name, _, date)| (name, date))
+            .collect()
+    }
+
+    /// Parses only the commits of a single git tag, which are the ones between
+    /// the given tag and its preceding tagged commit. This allows to load
+    /// releases lazily without parsing the whole log. The parsing results of
+    /// the journal are not touched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// if let Ok(tag) = journal.parse_tag("v1.2.0") {
+    ///     println!("{} contains {} commits", tag.name, tag.commits.len());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the tag does not exist or the parsing of its commits failed.
+    pub fn parse_tag(&mut self, tag_name: &str) -> Result<ParsedTag, Error> {
+        let repo = Repository::open(&self.path)?;
+        let tag_oid = self
+            .tags
+            .iter()
+            .find(|tag| tag.1 == tag_name)
+            .map(|tag| tag.0)
+            .ok_or_else(|| format_err!("Tag '{}' not found", tag_name))?;
+
+        // The predecessor is the first tagged commit within the history
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+        revwalk.push(tag_oid)?;
+        let mut predecessor = None;
+        for oid in revwalk {
+            let oid = oid?;
+            if oid != tag_oid && self.tags.iter().any(|tag| tag.0 == oid) {
+                predecessor = Some(oid);
+                break;
+            }
+        }
+        let revision_range = match predecessor {
+            Some(oid) => format!("{}..{}", oid, tag_oid),
+            None => tag_oid.to_string(),
+        };
+
+        let mut worker_vec = vec![];
+        for oid in walk_revision_range(&repo, &revision_range)? {
+            let commit = repo.find_commit(oid)?;
+            if self.is_excluded_commit(oid) || self.is_fixup_commit(&commit) {
+                continue;
+            }
+            worker_vec.push(self.walk_commit(&repo, &commit)?);
+        }
+        self.parse_walked_commits(&mut worker_vec, None);
+
+        let commit = repo.find_commit(tag_oid)?;
+        let parsed_tag = ParsedTag {
+            name: tag_name.to_owned(),
+            date: self.release_date(&[tag_name], &commit),
+            commits: vec![],
+            message_ids: (0..worker_vec.len()).collect(),
+        };
+        Ok(self
+            .assemble_tag(parsed_tag.clone(), &worker_vec, 0)
+            .unwrap_or(parsed_tag))
+    }
+
+    /// Parses the commits which are part of the revision range `range_b`, but
+    /// not of `range_a`, like the new commits of a release candidate compared
+    /// to the last one. The parsing results of the journal are not touched.
+    /// Invalid revision ranges result in a warning and no commits at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// for commit in journal.diff_ranges("HEAD~1", "HEAD") {
+    ///     println!("New: {}", commit.summary.text);
+    /// }
+    /// ```
+    pub fn diff_ranges(&mut self, range_a: &str, range_b: &str) -> Vec<ParsedCommit> {
+        match self.parse_range_difference(range_a, range_b) {
+            Ok(commits) => commits,
+            Err(e) => {
+                warn!("Unable to diff the revision ranges: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Parses the commits of `range_b` which are not part of `range_a`.
+    fn parse_range_difference(
+        &self,
+        range_a: &str,
+        range_b: &str,
+    ) -> Result<Vec<ParsedCommit>, Error> {
+        let repo = Repository::open(&self.path)?;
+        let oids_a = walk_revision_range(&repo, range_a)?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let mut worker_vec = vec![];
+        for oid in walk_revision_range(&repo, range_b)? {
+            if oids_a.contains(&oid) || self.is_excluded_commit(oid) {
+                continue;
+            }
+            let commit = repo.find_commit(oid)?;
+            if self.is_fixup_commit(&commit) {
+                continue;
+            }
+            worker_vec.push(self.walk_commit(&repo, &commit)?);
+        }
+        self.parse_walked_commits(&mut worker_vec, None);
+        Ok(worker_vec
+            .into_iter()
+            .filter_map(|walked| walked.parsed)
+            .collect())
+    }
+
+    /// Collects everything of a commit which is needed for the parsing.
+    fn walk_commit(&self, repo: &Repository, commit: &Commit) -> Result<WalkedCommit, Error> {
+        let oid = commit.id();
+        let message = match commit.message() {
+            Some(message) => message.to_owned(),
+            None => {
+                // Legacy repositories may contain commit messages in other encodings
+                warn!(
+                    "Commit {} contains invalid UTF-8, using a lossy conversion",
+                    oid
+                );
+                String::from_utf8_lossy(commit.message_bytes()).into_owned()
+            }
+        };
+
+        // Included fixup commits are parsed like the commit they belong to
+        let mut message = message.as_str();
+        while let Some(stripped) = FIXUP_PREFIXES
+            .iter()
+            .find_map(|prefix| message.strip_prefix(prefix))
+        {
+            message = stripped;
+        }
+        let message = message.to_owned();
+
+        // Commits without a category may get one based on the changed paths
+        let inferred_category = if self.config.infer_category_from_paths.is_empty() {
+            None
+        } else {
+            infer_category(repo, commit, &self.config.infer_category_from_paths)?
+        };
+
+        // Notes are attached to the commits only if they will be shown
+        let note = if self.config.show_notes {
+            repo.find_note(Some(&self.config.notes_ref), oid)
+                .ok()
+                .and_then(|note| note.message().map(|m| m.trim().to_owned()))
+        } else {
+            None
+        };
+
+        // The diff is only computed if the changed files will be shown
+        let files_changed = if self.config.show_files_changed {
+            changed_paths(repo, commit)?
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        // The parsed commit will be added later on
+        Ok(WalkedCommit {
+            message,
+            oid,
+            time: commit.time().seconds(),
+            inferred_category,
+            // Unsigned commits have no signature to extract
+            signed: self.config.show_signature_status && repo.extract_signature(&oid, None).is_ok(),
+            note,
+            files_changed,
+            parsed: None,
+        })
+    }
+
+    /// The date of a release with the given tags, which is the newest tagger
+    /// date if `release_date_source` is "tag" and the date of the tagged
+    /// commit otherwise.
+    fn release_date(&self, tag_names: &[&str], commit: &Commit) -> Date<Utc> {
+        let tagger_time = self
+            .tags
+            .iter()
+            .filter(|tag| tag_names.contains(&tag.1.as_str()))
+            .filter_map(|tag| tag.2)
+            .max();
+        let seconds = match tagger_time {
+            Some(time) if self.config.release_date_source == "tag" => time,
+            _ => commit.time().seconds(),
+        };
+        self.config.date_of(seconds)
+    }
+
+    /// Checks if the commit is an autosquash commit like `fixup! Fixed a bug`,
+    /// which is not part of the changelog unless `include_fixup_commits` is
+    /// configured.
+    fn is_fixup_commit(&self, commit: &Commit) -> bool {
+        let is_fixup = FIXUP_PREFIXES
+            .iter()
+            .any(|prefix| commit.message_bytes().starts_with(prefix.as_bytes()));
+        if is_fixup && !self.config.include_fixup_commits {
+            info!("Skipping commit {}: Fixup commit", commit.id());
+            return true;
+        }
+        false
+    }
+
+    /// Checks if the commit is excluded via its hash within the configuration.
+    fn is_excluded_commit(&self, oid: Oid) -> bool {
+        let oid = oid.to_string();
+        self.config
+            .exclude_commits
+            .iter()
+            .any(|excluded| !excluded.is_empty() && oid.starts_with(&excluded.to_lowercase()))
+    }
+
+    /// Parses the walked commits with the full CPU power.
+    fn parse_walked_commits(
+        &self,
+        worker_vec: &mut [WalkedCommit],
+        ignore_tags: Option<&[String]>,
+    ) {
+        worker_vec.par_iter_mut().for_each(|walked| {
+            match self
+                .parser
+                .parse_commit_message(&walked.message, Some(walked.oid))
+            {
+                Ok(ref parsed_message) if parsed_message.skip => {
+                    info!(
+                        "Skipping commit {}: Excluded from the changelog",
+                        walked.oid
+                    )
+                }
+                Ok(mut parsed_message) => {
+                    parsed_message.time = Some(walked.time);
+                    parsed_message.signed = walked.signed;
+                    parsed_message.summary.signed = walked.signed;
+                    parsed_message.note = walked.note.clone();
+                    parsed_message.files_changed = walked.files_changed.clone();
+                    if let Some(ref category) = walked.inferred_category {
+                        if parsed_message.summary.category.is_empty() {
+                            parsed_message.summary.category = category.clone();
+                        }
+                    }
+                    match ignore_tags {
+                        Some(tags) => {
+                            for tag in tags {
+                                // Filter out ignored tags
+                                if !parsed_message.contains_tag(Some(tag.as_str())) {
+                                    walked.parsed = Some(parsed_message.clone())
+                                }
+                            }
+                        }
+                        _ => walked.parsed = Some(parsed_message),
+                    }
+                }
+                Err(e) => warn!("Skipping commit: {}", e),
+            }
+        });
+    }
+
+    /// Adds the parsed commits to the tag via its message ids, where the
+    /// `worker_vec` starts at the id `first_id`. Returns `None` if no commit
+    /// is left.
+    fn assemble_tag(
+        &self,
+        mut parsed_tag: ParsedTag,
+        worker_vec: &[WalkedCommit],
+        first_id: usize,
+    ) -> Option<ParsedTag> {
+        for id in &parsed_tag.message_ids {
+            if let Some(parsed_commit) = worker_vec[*id - first_id].parsed.clone() {
+                if self.config.expand_squash_commits {
+                    parsed_tag.commits.extend(parsed_commit.expand_squash());
+                } else {
+                    parsed_tag.commits.push(parsed_commit);
+                }
+            }
+        }
+        if parsed_tag.commits.is_empty() {
+            None
+        } else {
+            parsed_tag.sort_commits(&self.config);
+            if self.config.commit_order == "oldest" {
+                parsed_tag.commits.reverse();
+            }
+            Some(parsed_tag)
+        }
+    }
+
+    /// Generates an output template from the current parsing results. An
+    /// already existing template keeps its header, footer and tags, where only
+    /// the newly found tags will be added. The template will be written to
+    /// `template.toml` within the repository if no `output` path is provided,
+    /// where missing parent directories will be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// journal
+    ///     .generate_template(None)
+    ///     .expect("Template generation failed.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the generation of the template was impossible.
+    pub fn generate_template(&self, output: Option<&str>) -> Result<(), Error> {
+        let mut tags = vec![];
+
+        // Get all the tags sorted alphabetically, where the default one comes first
+        for parsed_tag in &self.parser.result {
+            tags = parsed_tag.get_tags_unique(tags);
+        }
+        tags.retain(|tag| tag != parser::TOML_DEFAULT_KEY);
+        tags.insert(0, parser::TOML_DEFAULT_KEY.to_owned());
+
+        if tags.len() > 1 {
+            info!("Found tags: '{}'.", tags[1..].join(", "));
+        } else {
+            warn!("No tags found.");
+        }
+
+        // Start with the existing template to keep its customizations
+        let path_buf = match output {
+            Some(output) => PathBuf::from(output),
+            None => Path::new(&self.path).join("template.toml"),
+        };
+        let mut toml_map: Map<String, Value> = if path_buf.exists() {
+            toml::from_str(&fs::read_to_string(&path_buf)?)?
+        } else {
+            Map::new()
+        };
+        let mut existing_tags = vec![];
+        Self::collect_template_tags(&Value::Table(toml_map.clone()), &mut existing_tags);
+
+        // Create the toml representation of the new tags
+        let toml_tags = tags
+            .iter()
+            .filter(|tag| !existing_tags.contains(tag))
+            .map(|tag| {
+                let mut map = Map::new();
+                map.insert(parser::TOML_TAG.to_owned(), Value::String(tag.to_owned()));
+                map.insert(
+                    parser::TOML_NAME_KEY.to_owned(),
+                    Value::String(tag.to_owned()),
+                );
+                map.insert(parser::TOML_FOOTERS_KEY.to_owned(), Value::Array(vec![]));
+                Value::Table(map)
+            })
+            .collect::<Vec<Value>>();
+        match toml_map
+            .entry("tags")
+            .or_insert_with(|| Value::Array(vec![]))
+        {
+            Value::Array(array) => array.extend(toml_tags),
+            _ => bail!("The 'tags' of the existing template are not an array"),
+        }
+
+        let mut header_footer_map = Map::new();
+        header_footer_map.insert(parser::TOML_ONCE_KEY.to_owned(), Value::Boolean(false));
+        header_footer_map.insert(
+            parser::TOML_TEXT_KEY.to_owned(),
+            Value::String(String::new()),
+        );
+        toml_map
+            .entry(parser::TOML_HEADER_KEY)
+            .or_insert_with(|| Value::Table(header_footer_map.clone()));
+        toml_map
+            .entry(parser::TOML_FOOTER_KEY)
+            .or_insert_with(|| Value::Table(header_footer_map));
+
+        let toml = Value::Table(toml_map);
+
+        // Write toml to file
+        if let Some(parent) = path_buf.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let toml_string = toml::to_string(&toml)?;
+        let mut toml_file = File::create(&path_buf)?;
+        toml_file.write_all(toml_string.as_bytes())?;
+
+        info!("Template written to '{}'", path_buf.display());
+        Ok(())
+    }
+
+    /// Collects all tags of a template recursively, including the subtags.
+    fn collect_template_tags(value: &Value, tags: &mut Vec<String>) {
+        match value {
+            Value::Table(table) => {
+                if let Some(Value::String(tag)) = table.get(parser::TOML_TAG) {
+                    tags.push(tag.clone());
+                }
+                for value in table.values() {
+                    Self::collect_template_tags(value, tags);
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    Self::collect_template_tags(value, tags);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Prints the resulting log in a short or detailed variant. Will use the
+    /// template as an output formatter if provided.
+    ///
+    /// The template will be resolved in the following order:
+    ///
+    /// 1. The explicitly provided `template`
+    /// 2. The `default_template` of the configuration
+    /// 3. The path within the `GIT_JOURNAL_TEMPLATE` environment variable
+    /// 4. The builtin output without any template
+    ///
+    /// Relative paths of the last two are relative to the repository path.
+    /// An `output` file will be overwritten unless `append_output` is
+    /// configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// journal
+    ///     .print_log(true, None, None)
+    ///     .expect("Could not print short log.");
+    /// journal
+    ///     .print_log(false, None, None)
+    ///     .expect("Could not print detailed log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If some commit message could not be print.
+    pub fn print_log(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        output: Option<&str>,
+    ) -> Result<(), Error> {
+        self.print_log_with_format(compact, template, output, None)
+    }
+
+    /// Prints the resulting log like `print_log`, but with an explicit output
+    /// format, which is one of `"terminal"`, `"markdown"`, `"plain"`,
+    /// `"json-lines"` or `"digest"`. The `"markdown"` and `"plain"` formats
+    /// never contain any color escape sequences, whereas `"plain"` strips the
+    /// inline markdown as well. `"json-lines"` prints a JSON object per commit
+    /// and `"digest"` a line with the commit counts per release. If no
+    /// format is given, `"markdown"` will be used for the file and
+    /// `"terminal"` for the terminal output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// journal
+    ///     .print_log_with_format(true, None, None, Some("plain"))
+    ///     .expect("Could not print plain log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If some commit message could not be print.
+    pub fn print_log_with_format(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        output: Option<&str>,
+        format: Option<&str>,
+    ) -> Result<(), Error> {
+        let writer = self.render_log(compact, template, output.is_some(), format)?;
+
+        // Print the log to the file if necessary, which will be overwritten
+        // unless the incremental workflow appends to it
+        if let (Some(output), Output::Buffer(vec)) = (output, writer) {
+            let mut output_file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(self.config.append_output)
+                .truncate(!self.config.append_output)
+                .open(output)?;
+            output_file.write_all(&vec)?;
+            info!("Output written to '{}'.", output);
+        }
+
+        Ok(())
+    }
+
+    /// Checks if the `output` file is up to date, which means that it equals
+    /// the log printed by `print_log_with_format` apart from trailing
+    /// whitespace. This is meant for continuous integration, like `cargo fmt
+    /// --check`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&["HEAD"], &ParseOptions::new());
+    /// if let Err(e) = journal.check_log(true, None, "CHANGELOG.md", None) {
+    ///     println!("{}", e);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the output file is not up to date, where the error contains the
+    /// difference, or the log could not be printed.
+    pub fn check_log(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        output: &str,
+        format: Option<&str>,
+    ) -> Result<(), Error> {
+        let writer = self.render_log(compact, template, true, format)?;
+        let expected = writer
+            .as_str()
+            .ok_or_else(|| format_err!("The printed log is not valid UTF-8."))?;
+
+        // A missing output file is outdated as well
+        let existing = match fs::read_to_string(output) {
+            Ok(existing) => existing,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let trimmed_lines = |text: &str| {
+            let mut lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+            while lines.last() == Some(&"") {
+                lines.pop();
+            }
+            lines.into_iter().map(str::to_owned).collect::<Vec<_>>()
+        };
+        let existing_lines = trimmed_lines(&existing);
+        let expected_lines = trimmed_lines(expected);
+        if existing_lines != expected_lines {
+            bail!(
+                "The output file '{}' is not up to date:\n{}",
+                output,
+                line_diff(&existing_lines, &expected_lines)
+            );
+        }
+        info!("Output file '{}' is up to date.", output);
+        Ok(())
+    }
+
+    /// Prints the log into a buffer for files or to the terminal otherwise.
+    fn render_log(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        to_file: bool,
+        format: Option<&str>,
+    ) -> Result<Output, Error> {
+        // Choose the template
+        let default_template = self.default_template();
+        let used_template = match (&template, &default_template) {
+            (Some(_), _) | (_, None) => template,
+            (_, Some(ref default_template)) if default_template.exists() => {
+                info!("Using default template '{}'.", default_template.display());
+                default_template.to_str()
+            }
+            (_, Some(ref default_template)) => {
+                warn!(
+                    "The default template '{}' does not exist.",
+                    default_template.display()
+                );
+                None
+            }
+        };
+
+        // Prints the log to either the file or the terminal
+        let mut writer = if to_file {
+            Output::new_buffer()
+        } else {
+            Output::new_terminal_with_color(&self.config.color)
+        };
+
+        let format = format.unwrap_or(if to_file { "markdown" } else { "terminal" });
+        self.parser
+            .print_to(compact, used_template, format, to_file, &mut writer)?;
+        Ok(writer)
+    }
+}
+
+/// A pattern to match git tag names against.
+///
+/// The pattern is treated as a regular expression. If it is not a valid one,
+/// a plain substring match will be done instead.
+enum TagPattern {
+    Regex(Regex),
+    Substring(String),
+}
+
+impl TagPattern {
+    fn new(pattern: &str) -> Self {
+        match Regex::new(pattern) {
+            Ok(regex) => TagPattern::Regex(regex),
+            Err(e) => {
+                warn!(
+                    "Invalid tag pattern '{}', using substring matching: {}",
+                    pattern, e
+                );
+                TagPattern::Substring(pattern.to_owned())
+            }
+        }
+    }
+
+    fn is_match(&self, tag: &str) -> bool {
+        match self {
+            TagPattern::Regex(regex) => regex.is_match(tag),
+            TagPattern::Substring(pattern) => tag.contains(pattern.as_str()),
+        }
+    }
+}
+
+/// Walks a single revision range and returns all contained commits, sorted by
+/// time.
+///
+/// If a single revision is given, the whole history of it will be returned.
+///
+/// # Errors
+///
+/// Fails if the revision range is invalid.
+fn walk_revision_range(repo: &Repository, revision_range: &str) -> Result<Vec<Oid>, Error> {
+    Ok(revision_walk(repo, revision_range)?.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Collects the commits of all revision ranges without duplicates. The
+/// commits of a range stay together, since the ranges may cover histories
+/// whose commits interleave in time, where the range with the newest commit
+/// comes first.
+fn collect_revision_ranges(
+    repo: &Repository,
+    revision_ranges: &[&str],
+) -> Result<Vec<Vec<Oid>>, Error> {
+    let mut seen_oids = HashSet::new();
+    let mut timed_ranges = vec![];
+    for revision_range in revision_ranges {
+        let oids = walk_revision_range(repo, revision_range)?
+            .into_iter()
+            .filter(|oid| seen_oids.insert(*oid))
+            .collect::<Vec<_>>();
+        if let Some(&newest) = oids.first() {
+            timed_ranges.push((repo.find_commit(newest)?.time().seconds(), oids));
+        }
+    }
+    timed_ranges.sort_by_key(|&(time, _)| Reverse(time));
+    Ok(timed_ranges.into_iter().map(|(_, oids)| oids).collect())
+}
+
+/// Prepares the lazy revwalk of a single revision range, sorted by time.
+fn revision_walk<'repo>(
+    repo: &'repo Repository,
+    revision_range: &str,
+) -> Result<Revwalk<'repo>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TIME)?;
+
+    // Fill the revwalk with the selected revisions.
+    let revspec = repo.revparse(revision_range)?;
+    if revspec.mode().contains(git2::RevparseMode::SINGLE) {
+        // A single commit was given
+        let from = revspec
+            .from()
+            .ok_or_else(|| git2::Error::from_str("Could not set revision range start"))?;
+        revwalk.push(from.id())?;
+    } else {
+        // A specific commit range was given
+        let from = revspec
+            .from()
+            .ok_or_else(|| git2::Error::from_str("Could not set revision range start"))?;
+        let to = revspec
+            .to()
+            .ok_or_else(|| git2::Error::from_str("Could not set revision range end"))?;
+        revwalk.push(to.id())?;
+        if revspec.mode().contains(git2::RevparseMode::MERGE_BASE) {
+            let base = repo.merge_base(from.id(), to.id())?;
+            let o = repo.find_object(base, Some(ObjectType::Commit))?;
+            revwalk.push(o.id())?;
+        }
+        revwalk.hide(from.id())?;
+    }
+    Ok(revwalk)
+}
+
+/// Groups the commits of the tags into monthly tags, sorted descending by their
+/// names like `2024-03`. The `worker_vec` starts at the id `first_id`.
+fn group_into_months(
+    tags: &[ParsedTag],
+    worker_vec: &[WalkedCommit],
+    first_id: usize,
+    config: &Config,
+) -> Vec<ParsedTag> {
+    let mut months: BTreeMap<String, ParsedTag> = BTreeMap::new();
+    for id in tags.iter().flat_map(|tag| &tag.message_ids) {
+        let date = config.date_of(worker_vec[*id - first_id].time);
+        let name = format!("{}-{:02}", date.year(), date.month());
+        let month = months.entry(name.clone()).or_insert_with(|| ParsedTag {
+            name,
+            date,
+            commits: vec![],
+            message_ids: vec![],
+        });
+        month.date = month.date.max(date);
+        month.message_ids.push(*id);
+    }
+    months.into_iter().rev().map(|(_, month)| month).collect()
+}
+
+/// Groups the commits of the tags by their summary prefix like `JIRA-1234`,
+/// sorted by the prefixes. Commits without a prefix are grouped as "Other",
+/// which comes last. The `worker_vec` starts at the id `first_id`.
+fn group_into_prefixes(
+    tags: &[ParsedTag],
+    worker_vec: &[WalkedCommit],
+    first_id: usize,
+    config: &Config,
+) -> Vec<ParsedTag> {
+    let mut prefixes: BTreeMap<String, ParsedTag> = BTreeMap::new();
+    let mut other = ParsedTag {
+        name: "Other".to_owned(),
+        date: Utc.timestamp(0, 0).date(),
+        commits: vec![],
+        message_ids: vec![],
+    };
+    for id in tags.iter().flat_map(|tag| &tag.message_ids) {
+        let walked = &worker_vec[*id - first_id];
+        let group = match walked.parsed {
+            Some(ref parsed) if !parsed.summary.prefix.is_empty() => {
+                let name = parsed.summary.prefix.clone();
+                prefixes.entry(name.clone()).or_insert_with(|| ParsedTag {
+                    name,
+                    date: other.date,
+                    commits: vec![],
+                    message_ids: vec![],
+                })
+            }
+            _ => &mut other,
+        };
+        group.date = group.date.max(config.date_of(walked.time));
+        group.message_ids.push(*id);
+    }
+    prefixes.into_values().chain(iter::once(other)).collect()
+}
+
+/// Checks if a git tag should be treated as a release boundary.
+///
+/// The skip pattern always wins over the include pattern.
+fn is_release_tag(
+    name: &str,
+    skip_pattern: &TagPattern,
+    only_pattern: Option<&TagPattern>,
+) -> bool {
+    !skip_pattern.is_match(name) && only_pattern.map(|p| p.is_match(name)).unwrap_or(true)
+}
+
+/// Selects the release name out of multiple tags pointing to the same commit.
+///
+/// The "merge" preference joins all names, every other one prefers the first
+/// semantic version like name.
+fn select_tag_name(names: &[&str], preference: &str) -> String {
+    lazy_static! {
+        static ref RE_SEMVER_TAG: Regex = Regex::new(r"^v?\d+\.\d+(\.\d+)?").unwrap();
+    }
+    if preference == "merge" {
+        return names.join(", ");
+    }
+    names
+        .iter()
+        .find(|name| RE_SEMVER_TAG.is_match(name))
+        .unwrap_or(&names[0])
+        .to_string()
+}
+
+/// Checks if a commit can be safely skipped.
+///
+/// Can be skipped if none of the passed paths contain changes.
+///
+/// # Errors
+///
+/// Fails if any of the underlying Git operation fails.
+fn skip_commit(repo: &Repository, commit: &Commit, path_spec: &[String]) -> Result<bool, Error> {
+    let mut diff_opts = DiffOptions::new();
+    for spec in path_spec {
+        diff_opts.pathspec(spec);
+    }
+
+    let changed = commit
+        .parents()
+        .try_fold(false, |acc, parent| -> Result<bool, Error> {
+            Ok(acc || diffs_from_parent(repo, commit, &parent, &mut diff_opts)?)
+        })?;
+
+    Ok(!changed)
+}
+
+/// A commit collected during the revision walk, which will be parsed
+/// afterwards.
+struct WalkedCommit {
+    message: String,
+    oid: Oid,
+    time: i64,
+    inferred_category: Option<String>,
+    signed: bool,
+    note: Option<String>,
+    files_changed: Vec<String>,
+    parsed: Option<ParsedCommit>,
+}
+
+/// Returns the paths changed by the commit compared to its first parent.
+///
+/// # Errors
+///
+/// Fails if any of the underlying Git operation fails.
+fn changed_paths(repo: &Repository, commit: &Commit) -> Result<Vec<PathBuf>, Error> {
+    let parent_tree = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(Path::to_path_buf)
+        .collect())
+}
+
+/// Returns the category of the first path glob which matches all changed paths
+/// of the commit compared to its first parent.
+///
+/// # Errors
+///
+/// Fails if any of the underlying Git operation fails.
+fn infer_category(
+    repo: &Repository,
+    commit: &Commit,
+    path_categories: &BTreeMap<String, String>,
+) -> Result<Option<String>, Error> {
+    let paths = changed_paths(repo, commit)?;
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    for (glob, category) in path_categories {
+        let pathspec = Pathspec::new(iter::once(glob))?;
+        if paths
+            .iter()
+            .all(|path| pathspec.matches_path(path, PathspecFlags::DEFAULT))
+        {
+            return Ok(Some(category.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the changed lines between the old and new ones, prefixed by `-` for
+/// removed and `+` for added lines.
+fn line_diff(old: &[String], new: &[String]) -> String {
+    // Strip the common prefix and suffix, which are not part of the diff
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    // Too large tables are not worth it, so all lines are replaced instead
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|line| format!("-{}", line))
+            .chain(new.iter().map(|line| format!("+{}", line)))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    // The longest common subsequence of the remaining lines
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    diff.join("\n")
+}
+
+/// Checks if a commit has a diff from the specified parent commit
+///
+/// # Errors
+///
+/// Fails if any of the underlying Git operation fails.
+fn diffs_from_parent(
+    repo: &Repository,
+    commit: &Commit,
+    parent: &Commit,
+    opts: &mut DiffOptions,
+) -> Result<bool, Error> {
+    let a = parent.tree()?;
+    let b = commit.tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&a), Some(&b), Some(opts))?;
+    Ok(diff.deltas().len() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Timezone, verify::ProblemKind};
+    use std::{
+        cell::Cell,
+        ffi::OsString,
+        ops::Deref,
+        process,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    /// A git repository within a unique temporary directory, which will be
+    /// removed on drop.
+    struct TempRepo {
+        path: PathBuf,
+        repo: Repository,
+        time: Cell<i64>,
+    }
+
+    impl TempRepo {
+        fn new() -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let path = env::temp_dir().join(format!(
+                "gitjournal_{}_{}",
+                process::id(),
+                COUNT.fetch_add(1, Ordering::SeqCst)
+            ));
+            if path.exists() {
+                fs::remove_dir_all(&path).unwrap();
+            }
+            Self {
+                repo: Repository::init(&path).unwrap(),
+                path,
+                time: Cell::new(1_600_000_000),
+            }
+        }
+
+        fn journal(&self) -> GitJournal {
+            GitJournal::new(self.path.to_str().unwrap()).unwrap()
+        }
+
+        /// Commits the files with the message as their content, where every
+        /// commit is one second newer than the previous one.
+        fn commit(&self, message: &str, files: &[&str]) {
+            self.time.set(self.time.get() + 1);
+            self.commit_at(message, files, self.time.get());
+        }
+
+        fn commit_at(&self, message: &str, files: &[&str], seconds: i64) {
+            let workdir = self.repo.workdir().unwrap();
+            let mut index = self.repo.index().unwrap();
+            for file in files {
+                let path = workdir.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, message).unwrap();
+                index.add_path(Path::new(file)).unwrap();
+            }
+            index.write().unwrap();
+            let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let time = git2::Time::new(seconds, 0);
+            let signature = git2::Signature::new("Test", "test@example.com", &time).unwrap();
+            let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents = parent.iter().collect::<Vec<_>>();
+            self.repo
+                .commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+        }
+
+        /// Tags the `HEAD` commit.
+        fn tag_head(&self, name: &str) {
+            let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+            let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+            self.repo
+                .tag(name, head.as_object(), &signature, name, false)
+                .unwrap();
+        }
+
+        /// Commits the messages of every release and tags its last commit.
+        fn commit_releases(&self, releases: &[(&str, &[&str])]) {
+            for (tag, messages) in releases {
+                for message in messages.iter() {
+                    self.commit(message, &["file"]);
+                }
+                self.tag_head(tag);
+            }
+        }
+    }
+
+    impl Deref for TempRepo {
+        type Target = Repository;
+
+        fn deref(&self) -> &Repository {
+            &self.repo
+        }
+    }
+
+    impl Drop for TempRepo {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn new() {
+        assert!(GitJournal::new(".").is_ok());
+        let res = GitJournal::new("/dev/null");
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn setup_succeed() {
+        let path = ".";
+        let journal = GitJournal::new(path);
+        assert!(journal.is_ok());
+        assert!(journal.unwrap().setup().is_ok());
+        assert!(GitJournal::new(path).is_ok());
+    }
+
+    #[test]
+    fn setup_failed() {
+        let journal = GitJournal::new("./tests/test_repo");
+        assert!(journal.is_ok());
+        let res = journal.unwrap().setup();
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_1").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_2").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_3() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_3").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_4() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_4").is_ok());
+    }
+
+    fn verify_failure(path: &str) {
+        let journal = GitJournal::new(".").unwrap();
+        let res = journal.verify(path);
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn verify_commit_msg_with_bom() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .verify("./tests/commit_messages/success_bom")
+            .is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_template_env() {
+        let path = Path::new("repo");
+        let env_value = Some(OsString::from("tests/template.toml"));
+        assert_eq!(
+            resolve_template(path, None, env_value.clone()),
+            Some(path.join("tests/template.toml"))
+        );
+        assert_eq!(resolve_template(path, None, None), None);
+
+        // The configuration takes precedence over the environment
+        assert_eq!(
+            resolve_template(path, Some("CHANGELOG.toml"), env_value),
+            Some(path.join("CHANGELOG.toml"))
+        );
+
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.default_template = Some("tests/template.toml".to_owned());
+        assert!(
+            journal
+                .verify_report("./tests/commit_messages/success_1")
+                .unwrap()
+                .valid
+        );
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_1() {
+        verify_failure("./tests/commit_messages/failure_1");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_2() {
+        verify_failure("./tests/commit_messages/failure_2");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_3() {
+        verify_failure("./tests/commit_messages/failure_3");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_1() {
+        verify_failure("./tests/commit_messages/failure_4");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_2() {
+        verify_failure("./tests/commit_messages/failure_5");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_3() {
+        verify_failure("./tests/commit_messages/failure_6");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_tag() {
+        let journal = GitJournal::new("./tests/test_repo2").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_1").is_err());
+        assert!(journal.verify("./tests/commit_messages/success_3").is_err());
+    }
+
+    #[test]
+    fn verify_report_success() {
+        let journal = GitJournal::new(".").unwrap();
+        let report = journal
+            .verify_report("./tests/commit_messages/success_1")
+            .unwrap();
+        assert!(report.valid);
+        assert!(report.problems.is_empty());
+    }
+
+    #[test]
+    fn verify_report_failure() {
+        let journal = GitJournal::new(".").unwrap();
+        let report = journal
+            .verify_report("./tests/commit_messages/failure_1")
+            .unwrap();
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].kind, ProblemKind::Parse);
+        assert_eq!(report.problems[0].line, Some(1));
+        assert_eq!(report.problems[0].column, Some(1));
+        assert!(journal.verify_report("TEST").is_err());
+    }
+
+    #[test]
+    fn verify_require_body() {
+        let mut journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .verify("./tests/commit_messages/removed_without_body")
+            .is_ok());
+
+        journal.config.require_body_for = vec!["Removed".to_owned()];
+        assert!(journal
+            .verify("./tests/commit_messages/removed_with_body")
+            .is_ok());
+        let report = journal
+            .verify_report("./tests/commit_messages/removed_without_body")
+            .unwrap();
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].kind, ProblemKind::MissingBody);
+        assert_eq!(report.problems[0].line, Some(1));
+        assert!(report.problems[0].message.contains("'Removed'"));
+        assert!(journal.verify("./tests/commit_messages/success_1").is_ok());
+    }
+
+    #[test]
+    fn verify_autofix() {
+        let repo = TempRepo::new();
+        let path = repo.path.join("COMMIT_EDITMSG");
+        fs::copy("./tests/commit_messages/autofix_1", &path).unwrap();
+        let path = path.to_str().unwrap();
+
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify(path).is_err());
+        assert!(journal.autofix(path).is_ok());
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "JIRA-1234 [Added] my feature\n\nfixed-width fonts are used here  \n\
+             with a hard line break\n\n- [Fixed] a list item\n* [Changed] another list item\n"
+        );
+
+        fs::copy("./tests/commit_messages/failure_1", path).unwrap();
+        assert!(journal.autofix(path).is_err());
+    }
+
+    #[test]
+    fn verify_body_length() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.max_body_lines = Some(100);
+        journal.config.max_body_line_length = Some(120);
+        assert!(journal
+            .verify("./tests/commit_messages/success_huge")
+            .is_ok());
+
+        journal.config.max_body_lines = Some(3);
+        journal.config.max_body_line_length = Some(30);
+        let report = journal
+            .verify_report("./tests/commit_messages/success_1")
+            .unwrap();
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems.len(), 2);
+        assert!(report
+            .problems
+            .iter()
+            .all(|p| p.kind == ProblemKind::BodyLength));
+        assert_eq!(report.problems[0].line, Some(6));
+        assert_eq!(report.problems[1].line, Some(3));
+        assert_eq!(report.problems[1].column, Some(31));
+    }
+
+    #[test]
+    fn verify_unreleased() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[("v1", &["Added the library", "this is not valid"])]);
+        repo.commit("Fixed the library", &["file"]);
+        repo.commit("Added the tests", &["file"]);
+
+        let journal = repo.journal();
+        assert!(journal.verify_unreleased().is_ok());
+
+        repo.commit("wip", &["file"]);
+        repo.commit("Removed the tests", &["file"]);
+        let invalid = repo.revparse_single("HEAD~1").unwrap().id();
+        let error = journal.verify_unreleased().unwrap_err().to_string();
+        assert!(error.contains(&invalid.to_string()));
+        let valid = repo.revparse_single("HEAD").unwrap().id();
+        assert!(!error.contains(&valid.to_string()));
+    }
+
+    #[test]
+    fn verify_category_override() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.category_override_trailer = Some("Category".to_owned());
+        journal.parser.config = journal.config.clone();
+        let message = fs::read_to_string("./tests/commit_messages/category_override_1").unwrap();
+        let commit = journal.parser.parse_commit_message(&message, None).unwrap();
+        assert_eq!(commit.summary.category, "Fixed");
+        assert!(journal
+            .verify_report("./tests/commit_messages/category_override_1")
+            .unwrap()
+            .problems
+            .is_empty());
+
+        let report = journal
+            .verify_report("./tests/commit_messages/category_override_2")
+            .unwrap();
+        assert!(report.valid);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].kind, ProblemKind::UnknownCategory);
+        assert_eq!(report.problems[0].line, Some(3));
+        assert_eq!(report.problems[0].column, Some(11));
+
+        // Categories are matched as regular expressions
+        journal.config.categories = vec!["Add(ed)?".to_owned(), "Fix(ed)?".to_owned()];
+        journal.parser.config = journal.config.clone();
+        assert!(journal
+            .verify_report("./tests/commit_messages/success_1")
+            .unwrap()
+            .problems
+            .is_empty());
+        assert!(journal
+            .verify_report("./tests/commit_messages/category_override_1")
+            .unwrap()
+            .problems
+            .is_empty());
+    }
+
+    #[test]
+    fn categories_from_template() {
+        let repo = TempRepo::new();
+        let template = repo.path.join("template.toml");
+        fs::write(
+            &template,
+            "[[tag]]\ntag = \"default\"\nname = \"Other\"\n\n[[tag]]\ntag = \"Security\"\n\
+             name = \"Security\"\n\n[[tag]]\n[[tag.subtag]]\ntag = \"Docs\"\nname = \"Docs\"\n",
+        )
+        .unwrap();
+
+        let mut journal = GitJournal::new(".").unwrap();
+        let mut config = journal.config.clone();
+        config.categories = vec![];
+        config.default_template = Some(template.to_str().unwrap().to_owned());
+        config.categories_from_template = true;
+        journal.set_config(config);
+        assert_eq!(journal.config.categories, vec!["Security", "Docs"]);
+
+        let commit = journal
+            .parser
+            .parse_commit_message("Security Fix the overflow\n\n- Docs Add an example", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "Security");
+        assert!(journal
+            .parser
+            .parse_commit_message("Fixed the overflow", None)
+            .is_err());
+    }
+
+    #[test]
+    fn parse_log_unmapped_tags() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library :api:", &["file"]);
+        repo.commit("Fixed the library :aip:\n\n- Added docs :docs:", &["file"]);
+        repo.commit("Added internals :internal:", &["file"]);
+        fs::write(
+            repo.path.join("template.toml"),
+            "[[tag]]\ntag = \"default\"\nname = \"Other\"\n\n[[tag]]\ntag = \"api\"\n\
+             name = \"API\"\n",
+        )
+        .unwrap();
+
+        let mut journal = repo.journal();
+        journal.config.warn_unmapped_tags = true;
+        journal.config.excluded_commit_tags = vec!["internal".to_owned()];
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert!(journal.unmapped_tags().unwrap().is_empty());
+
+        journal.config.default_template = Some("template.toml".to_owned());
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert_eq!(journal.unmapped_tags().unwrap(), vec!["aip", "docs"]);
+    }
+
+    #[test]
+    fn verify_lenient_summary() {
+        verify_failure("./tests/commit_messages/lenient_1");
+
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.lenient_summary = true;
+        journal.parser.config = journal.config.clone();
+        let message = fs::read_to_string("./tests/commit_messages/lenient_1").unwrap();
+        let commit = journal.parser.parse_commit_message(&message, None).unwrap();
+        assert_eq!(commit.summary.category, "");
+        assert_eq!(
+            commit.summary.text,
+            "This imported commit has no category at all"
+        );
+        assert_eq!(commit.body.len(), 2);
+        assert!(journal.verify("./tests/commit_messages/lenient_1").is_ok());
+    }
+
+    #[test]
+    fn verify_disallowed_phrases() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.disallowed_phrases =
+            vec!["WIP".to_owned(), "fixup!".to_owned(), "TODO".to_owned()];
+        let report = journal
+            .verify_report("./tests/commit_messages/disallowed_1")
+            .unwrap();
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems[0].kind, ProblemKind::DisallowedPhrase);
+        assert_eq!(
+            report.problems[0].message,
+            "Disallowed phrase 'fixup!' found."
+        );
+        assert_eq!(report.problems[0].line, Some(1));
+        assert_eq!(report.problems[0].column, Some(1));
+
+        journal.config.disallowed_phrases = vec!["fixup".to_owned()];
+        assert_eq!(
+            journal
+                .verify_report("./tests/commit_messages/disallowed_1")
+                .unwrap()
+                .problems[0]
+                .message,
+            "Disallowed phrase 'fixup' found."
+        );
+        journal.config.disallowed_phrases = vec!["fix".to_owned()];
+        assert!(journal
+            .verify_report("./tests/commit_messages/disallowed_1")
+            .unwrap()
+            .problems
+            .iter()
+            .all(|problem| problem.kind != ProblemKind::DisallowedPhrase));
+
+        let report = journal
+            .verify_report("./tests/commit_messages/disallowed_2")
+            .unwrap();
+        assert!(report.valid);
+        assert!(report.problems.is_empty());
+
+        journal.config.disallowed_phrases = vec![r"TODOs?\b".to_owned()];
+        journal.config.disallowed_phrases_regex = true;
+        let report = journal
+            .verify_report("./tests/commit_messages/disallowed_2")
+            .unwrap();
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].line, Some(1));
+        assert_eq!(report.problems[0].column, Some(27));
+
+        journal.config.disallowed_phrases = vec!["TODO(".to_owned()];
+        assert!(journal
+            .verify_report("./tests/commit_messages/disallowed_2")
+            .is_err());
+    }
+
+    #[test]
+    fn verify_imperative_mood() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.imperative_mood_check = true;
+        let report = journal
+            .verify_report("./tests/commit_messages/mood_1")
+            .unwrap();
+        assert!(report.valid);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].kind, ProblemKind::ImperativeMood);
+        assert_eq!(report.problems[0].severity, Severity::Warn);
+        assert_eq!(report.problems[0].line, Some(1));
+        assert_eq!(report.problems[0].column, Some(9));
+
+        let report = journal
+            .verify_report("./tests/commit_messages/mood_2")
+            .unwrap();
+        assert!(report.problems.is_empty());
+
+        journal.config.imperative_mood_exceptions = vec![];
+        let report = journal
+            .verify_report("./tests/commit_messages/mood_2")
+            .unwrap();
+        assert_eq!(report.problems.len(), 1);
+
+        journal
+            .config
+            .verify_severity
+            .insert("imperative_mood".to_owned(), Severity::Error);
+        assert!(journal.verify("./tests/commit_messages/mood_1").is_err());
+
+        journal
+            .config
+            .verify_severity
+            .insert("imperative_mood".to_owned(), Severity::Off);
+        let report = journal
+            .verify_report("./tests/commit_messages/mood_1")
+            .unwrap();
+        assert!(report.problems.is_empty());
+
+        journal.config.imperative_mood_check = false;
+        journal.config.verify_severity.clear();
+        let report = journal
+            .verify_report("./tests/commit_messages/mood_1")
+            .unwrap();
+        assert!(report.problems.is_empty());
+    }
+
+    #[test]
+    fn verify_spellcheck() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.spellcheck_wordlist = Some("tests/wordlist".to_owned());
+        let report = journal
+            .verify_report("./tests/commit_messages/spelling_1")
+            .unwrap();
+        assert!(report.valid);
+        assert_eq!(report.problems_with(Severity::Error).count(), 0);
+        assert_eq!(report.problems_with(Severity::Warn).count(), 2);
+        assert_eq!(report.problems[0].kind, ProblemKind::Spelling);
+        assert_eq!(report.problems[0].line, Some(1));
+        assert_eq!(report.problems[0].column, Some(25));
+        assert!(journal.verify("./tests/commit_messages/spelling_1").is_ok());
+
+        journal.config.spellcheck_strict = true;
+        let report = journal
+            .verify_report("./tests/commit_messages/spelling_1")
+            .unwrap();
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems.len(), 2);
+        assert!(journal
+            .verify("./tests/commit_messages/spelling_1")
+            .is_err());
+
+        assert!(journal.verify("./tests/commit_messages/success_2").is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_1() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert_eq!(journal.tags.len(), 2);
+        assert_eq!(journal.parser.result.len(), 0);
+        assert_eq!(journal.config.show_prefix, false);
+        assert_eq!(journal.config.colored_output, true);
+        assert_eq!(journal.config.show_commit_hash, false);
+        assert_eq!(journal.config.excluded_commit_tags.len(), 0);
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), journal.tags.len() + 1);
+        assert_eq!(journal.parser.result[0].commits.len(), 15);
+        assert_eq!(journal.parser.result[1].commits.len(), 1);
+        assert_eq!(journal.parser.result[2].commits.len(), 2);
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(false, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+        assert!(journal
+            .print_log(true, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_2() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal.parse_log(&["HEAD"], &ParseOptions::new()).is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "Unreleased");
+        assert_eq!(journal.parser.result[1].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(false, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+        assert!(journal
+            .print_log(true, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_3() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().skip_unreleased(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 1);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(false, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+        assert!(journal
+            .print_log(true, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_4() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(
+                &["HEAD"],
+                &ParseOptions::new().max_tags_count(2).skip_unreleased(true)
+            )
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert_eq!(journal.parser.result[1].name, "v1");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(false, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+        assert!(journal
+            .print_log(true, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_5() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&["v1..v2"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 1);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(false, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+        assert!(journal
+            .print_log(true, Some("./tests/template.toml"), Some("CHANGELOG.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_6() {
+        let mut journal = GitJournal::new("./tests/test_repo2").unwrap();
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_multiple_ranges() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&["v1", "v2..HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "Unreleased");
+        assert_eq!(journal.parser.result[1].name, "v1");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+    }
+
+    #[test]
+    fn parse_log_interleaved_ranges() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        repo.tag_head("v1.0");
+        let main = repo.head().unwrap().name().unwrap().to_owned();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("maintenance", &base, false).unwrap();
+
+        // The commits of both branches alternate in time
+        let on_branch = |branch: &str, message: &str| {
+            repo.set_head(branch).unwrap();
+            repo.commit(message, &["file"]);
+        };
+        on_branch(&main, "Added the feature");
+        repo.tag_head("v2.0");
+        on_branch("refs/heads/maintenance", "Fixed the library");
+        on_branch(&main, "Added the tests");
+        on_branch("refs/heads/maintenance", "Fixed the documentation");
+        repo.tag_head("v1.1");
+        on_branch(&main, "Fixed the tests");
+        repo.tag_head("v2.1");
+        repo.commit("Added the unreleased things", &["file"]);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(
+                &["v1.0..v1.1", "v2.0..v2.1"],
+                &ParseOptions::new().all(true),
+            )
+            .unwrap();
+        let tags = journal
+            .parser
+            .result
+            .iter()
+            .map(|tag| {
+                let texts = tag
+                    .commits
+                    .iter()
+                    .map(|commit| commit.summary.text.as_str())
+                    .collect::<Vec<_>>();
+                (tag.name.as_str(), texts)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                ("v2.1", vec!["the tests", "the tests"]),
+                ("v1.1", vec!["the documentation", "the library"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_log_commit_order() {
+        let repo = TempRepo::new();
+        for message in &["Added the library", "Fixed the parser", "Added the tests"] {
+            repo.commit(message, &["file"]);
+        }
+
+        let mut summaries = vec![];
+        for order in &["newest", "oldest"] {
+            let mut journal = repo.journal();
+            journal.config.commit_order = (*order).to_owned();
+            journal
+                .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+                .unwrap();
+            summaries.push(
+                journal.parser.result[0]
+                    .commits
+                    .iter()
+                    .map(|commit| commit.summary.text.clone())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert_eq!(summaries[0], vec!["the tests", "the parser", "the library"]);
+        assert_eq!(summaries[1], vec!["the library", "the parser", "the tests"]);
+    }
+
+    #[test]
+    fn parse_log_progress() {
+        let repo = TempRepo::new();
+        for message in &["Added the library", "Fixed the library", "Added the tests"] {
+            repo.commit(message, &["file"]);
+        }
+
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut journal = repo.journal();
+        let progress_calls = calls.clone();
+        journal.set_progress(move |current, total| {
+            progress_calls.lock().unwrap().push((current, total))
+        });
+        journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn parse_log_limit() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1", &["Added the library", "Fixed the library"]),
+            ("v2", &["Added the tests", "Fixed the tests"]),
+        ]);
+        repo.commit("Added the unreleased things", &["file"]);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().limit(4))
+            .unwrap();
+        let result = &journal.parser.result;
+        assert_eq!(result.iter().map(|tag| tag.commits.len()).sum::<usize>(), 4);
+        assert_eq!(
+            result
+                .iter()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Unreleased", "v2", "v1"]
+        );
+        assert_eq!(result[2].commits.len(), 1);
+    }
+
+    #[test]
+    fn parse_log_all_and_tags_count() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1", &["Added the v1 feature"]),
+            ("v2", &["Added the v2 feature"]),
+            ("v3", &["Added the v3 feature"]),
+        ]);
+        repo.commit("Added the unreleased things", &["file"]);
+
+        let tag_names = |max_tags_count, all| -> Result<Vec<String>, Error> {
+            let mut journal = repo.journal();
+            journal.parse_log(
+                &["HEAD"],
+                &ParseOptions::new().all(all).max_tags_count(max_tags_count),
+            )?;
+            Ok(journal
+                .parser
+                .result
+                .into_iter()
+                .map(|tag| tag.name)
+                .collect())
+        };
+
+        // Parsing all commits with a tags count is contradictory
+        assert!(tag_names(2, true)
+            .unwrap_err()
+            .to_string()
+            .contains("contradicts a tags count of 2"));
+        assert_eq!(
+            tag_names(0, true).unwrap(),
+            vec!["Unreleased", "v3", "v2", "v1"]
+        );
+        assert_eq!(tag_names(2, false).unwrap(), vec!["Unreleased", "v3", "v2"]);
+    }
+
+    #[test]
+    fn parse_log_streaming() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1", &["Added the library", "Fixed the library"]),
+            ("v2", &["Added the tests\n\n- [Fixed] the library"]),
+        ]);
+        repo.commit("Added the unreleased things", &["file"]);
+
+        let mut journal = repo.journal();
+        let mut events = vec![];
+        journal
+            .parse_log_streaming(&["HEAD"], &ParseOptions::new().all(true), |event| {
+                events.push(event)
+            })
+            .unwrap();
+        assert!(journal.parser.result.is_empty());
+        assert_eq!(events.len(), 10);
+        assert_eq!(events.last(), Some(&ParseEvent::TagEnd));
+
+        // The events contain the same tags as the batch result
+        let mut tags: Vec<ParsedTag> = vec![];
+        for event in events {
+            match event {
+                ParseEvent::TagStart { name, date } => tags.push(ParsedTag {
+                    name,
+                    date,
+                    commits: vec![],
+                    message_ids: vec![],
+                }),
+                ParseEvent::Commit(commit) => tags.last_mut().unwrap().commits.push(commit),
+                ParseEvent::TagEnd => {}
+            }
+        }
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        for tag in &mut journal.parser.result {
+            tag.message_ids.clear();
+        }
+        assert_eq!(tags, journal.parser.result);
+    }
+
+    #[test]
+    fn parse_log_invalid_utf8() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+
+        // Latin-1 encoded "Fixed the caf\u{e9}"
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let mut buffer = repo
+            .commit_create_buffer(&signature, &signature, "", &head.tree().unwrap(), &[&head])
+            .unwrap()
+            .to_vec();
+        buffer.extend(b"Fixed the caf\xe9");
+        let oid = repo
+            .odb()
+            .unwrap()
+            .write(git2::ObjectType::Commit, &buffer)
+            .unwrap();
+        repo.reference("refs/heads/master", oid, true, "latin-1")
+            .unwrap();
+        repo.set_head("refs/heads/master").unwrap();
+        assert!(repo.find_commit(oid).unwrap().message().is_none());
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 2);
+        assert!(commits
+            .iter()
+            .any(|commit| commit.summary.text == "the caf\u{fffd}"));
+    }
+
+    #[test]
+    fn parse_log_group_by_month() {
+        let repo = TempRepo::new();
+        for (message, time) in &[
+            ("Added the library", Utc.ymd(2024, 2, 28).and_hms(12, 0, 0)),
+            ("Fixed the library", Utc.ymd(2024, 3, 1).and_hms(12, 0, 0)),
+            ("Added the tests", Utc.ymd(2024, 3, 15).and_hms(12, 0, 0)),
+        ] {
+            repo.commit_at(message, &["file"], time.timestamp());
+        }
+        repo.tag_head("v1");
+
+        let mut journal = repo.journal();
+        journal.config.group_by = "month".to_owned();
+        journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+        let result = &journal.parser.result;
+        assert_eq!(
+            result
+                .iter()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2024-03", "2024-02"]
+        );
+        assert_eq!(result[0].commits.len(), 2);
+        assert_eq!(result[0].date, Utc.ymd(2024, 3, 15));
+        assert_eq!(result[1].commits.len(), 1);
+    }
+
+    #[test]
+    fn parse_log_group_by_prefix() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[("v1", &["JIRA-2 Added the library", "Added the readme"])]);
+        for message in &[
+            "JIRA-1 Fixed the library",
+            "OPS-7 Added the pipeline",
+            "JIRA-2 Fixed the tests",
+        ] {
+            repo.commit(message, &["file"]);
+        }
+
+        let mut journal = repo.journal();
+        journal.config.group_by = "prefix".to_owned();
+        journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+        let result = &journal.parser.result;
+        assert_eq!(
+            result
+                .iter()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["JIRA-1", "JIRA-2", "OPS-7", "Other"]
+        );
+        let summaries = |tag: &ParsedTag| {
+            tag.commits
+                .iter()
+                .map(|commit| commit.summary.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summaries(&result[0]), vec!["the library"]);
+        assert_eq!(summaries(&result[1]), vec!["the tests", "the library"]);
+        assert_eq!(summaries(&result[3]), vec!["the readme"]);
+    }
+
+    #[test]
+    fn parsed_tags_json() {
+        let repo = TempRepo::new();
+        let time = Utc.ymd(2024, 3, 1).and_hms(12, 0, 0).timestamp();
+        repo.commit_at(
+            "Added the library :api:\n\n- [Fixed] a bug\n\nReviewed-by: Me",
+            &["file"],
+            time,
+        );
+        repo.tag_head("v1");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let mut journal = repo.journal();
+        journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(journal.parsed_tags()).unwrap()).unwrap();
+        let tag = &json[0];
+        assert_eq!(tag["name"], "v1");
+        assert_eq!(tag["date"], "2024-03-01");
+        let commit = &tag["commits"][0];
+        assert_eq!(commit["oid"], head.id().to_string());
+        assert_eq!(commit["time"], time);
+        assert_eq!(commit["summary"]["category"], "Added");
+        assert_eq!(commit["summary"]["text"], "the library");
+        assert_eq!(commit["summary"]["tags"][0], "api");
+        assert_eq!(commit["body"][0]["List"][0]["category"], "Fixed");
+        assert_eq!(commit["footer"][0]["key"], "Reviewed-by");
+    }
+
+    #[test]
+    fn parse_log_empty_result() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log_with_filter(&["HEAD"], &ParseOptions::new().all(true), |_| false)
+            .unwrap();
+        assert!(journal.parser.result.is_empty());
+        let warnings = journal.result_warnings().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("No commits matched the revision range"));
+
+        let mut writer = Output::new_buffer();
+        journal
+            .parser
+            .print(false, None, "markdown", &mut writer)
+            .unwrap();
+        assert_eq!(writer.as_str(), Some(""));
+
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        assert!(journal.result_warnings().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_log_skip_commits() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        for fixture in &["skip_1", "skip_2"] {
+            let message = fs::read_to_string(Path::new("tests/commit_messages").join(fixture));
+            repo.commit(&message.unwrap(), &["file"]);
+        }
+        let excluded = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        let mut journal = repo.journal();
+        journal.config.exclude_commits = vec![excluded.to_string()[..7].to_owned()];
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary.text, "the library");
+        assert!(journal.verify("./tests/commit_messages/skip_1").is_ok());
+    }
+
+    #[test]
+    fn print_log_overwrite_output() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        let output = repo.path.join("CHANGELOG.md");
+        let output = output.to_str().unwrap();
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        journal.print_log(true, None, Some(output)).unwrap();
+        let changelog = fs::read_to_string(output).unwrap();
+        journal.print_log(true, None, Some(output)).unwrap();
+        assert_eq!(fs::read_to_string(output).unwrap(), changelog);
+
+        journal.config.append_output = true;
+        journal.print_log(true, None, Some(output)).unwrap();
+        assert_eq!(fs::read_to_string(output).unwrap(), changelog.repeat(2));
+    }
+
+    #[test]
+    fn parse_log_branch() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("release-2.x", &base, false).unwrap();
+        repo.commit("Added a new feature", &["file"]);
+        let head = repo.head().unwrap().name().unwrap().to_owned();
+        repo.set_head("refs/heads/release-2.x").unwrap();
+        repo.commit("Fixed the backport", &["file"]);
+        repo.set_head(&head).unwrap();
+
+        let mut journal = repo.journal();
+        assert!(journal.parse_log_branch("missing").is_err());
+        journal.parse_log_branch("release-2.x").unwrap();
+        let texts = journal.parser.result[0]
+            .commits
+            .iter()
+            .map(|c| c.summary.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["the backport", "the library"]);
+    }
+
+    #[test]
+    fn check_log_stale_output() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        let output = repo.path.join("CHANGELOG.md");
+        let output = output.to_str().unwrap();
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        assert!(journal.check_log(true, None, output, None).is_err());
+        journal.print_log(true, None, Some(output)).unwrap();
+        assert!(journal.check_log(true, None, output, None).is_ok());
+
+        // Trailing whitespace is ignored
+        let changelog = fs::read_to_string(output).unwrap();
+        fs::write(output, changelog.replace("\n", "  \n") + "\n\n").unwrap();
+        assert!(journal.check_log(true, None, output, None).is_ok());
+
+        repo.commit("Fixed the library", &["file"]);
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let error = journal
+            .check_log(true, None, output, None)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("\n+- [Fixed] the library"));
+    }
+
+    #[test]
+    fn line_diff_changes() {
+        let lines = |text: &str| text.lines().map(str::to_owned).collect::<Vec<_>>();
+        assert_eq!(
+            line_diff(&lines("a\nb\nc\nd"), &lines("a\nx\nc\nd\ne")),
+            "-b\n+x\n+e"
+        );
+        assert_eq!(line_diff(&lines("a"), &lines("a")), "");
+
+        // Large changes fall back to replacing all differing lines
+        let numbers = (0..2000).map(|i| i.to_string());
+        let old = lines("a\n")
+            .into_iter()
+            .chain(numbers.clone())
+            .collect::<Vec<_>>();
+        let new = lines("a\n")
+            .into_iter()
+            .chain(numbers.rev())
+            .collect::<Vec<_>>();
+        let diff = line_diff(&old, &new);
+        assert_eq!(diff.lines().count(), 4000);
+        assert!(diff.starts_with("-0\n-1\n"));
+        assert!(diff.ends_with("+1\n+0"));
+    }
+
+    #[test]
+    fn parse_log_fixup_commits() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["file"]);
+        for fixture in &["fixup_1", "fixup_2"] {
+            let message = fs::read_to_string(Path::new("tests/commit_messages").join(fixture));
+            repo.commit(&message.unwrap(), &["file"]);
+        }
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary.text, "the library");
+
+        let mut journal = repo.journal();
+        journal.config.include_fixup_commits = true;
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 3);
+        assert_eq!(commits[0].summary.category, "Added");
+        assert_eq!(commits[0].summary.text, "the thing");
+        assert_eq!(commits[1].summary.category, "Fixed");
+    }
+
+    #[test]
+    fn parse_log_with_filter() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log_with_filter(&["HEAD"], &ParseOptions::new().all(true), |_| { false })
+            .is_ok());
+        assert!(journal.parser.result.is_empty());
+
+        assert!(journal
+            .parse_log_with_filter(&["HEAD"], &ParseOptions::new().all(true), |commit| commit
+                .message()
+                .map(|m| m.contains("[Added]"))
+                .unwrap_or(false))
+            .is_ok());
+        assert!(!journal.parser.result.is_empty());
+        assert!(journal
+            .parser
+            .result
+            .iter()
+            .flat_map(|tag| &tag.commits)
+            .all(|commit| commit.summary.category == "Added"));
+    }
+
+    #[test]
+    fn parse_tag() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        let tag = journal.parse_tag("v1").unwrap();
+        assert_eq!(tag.name, "v1");
+        assert!(!tag.commits.is_empty());
+        assert!(journal.parser.result.is_empty());
+        assert!(journal.parse_tag("not_existing").is_err());
+    }
+
+    #[test]
+    fn releases() {
+        let repo = TempRepo::new();
+        for (tag, date) in &[("v1", Utc.ymd(2020, 1, 2)), ("v2", Utc.ymd(2020, 3, 4))] {
+            let time = date.and_hms(12, 0, 0).timestamp();
+            repo.commit_at("Added something", &["file"], time);
+            repo.tag_head(tag);
+        }
+
+        let journal = repo.journal();
+        assert_eq!(
+            journal.releases(),
+            vec![
+                ("v2".to_owned(), Utc.ymd(2020, 3, 4)),
+                ("v1".to_owned(), Utc.ymd(2020, 1, 2))
+            ]
+        );
+        assert!(journal.parser.result.is_empty());
+    }
+
+    #[test]
+    fn parse_log_release_date() {
+        let repo = TempRepo::new();
+        let commit_time = Utc.ymd(2020, 1, 2).and_hms(12, 0, 0).timestamp();
+        repo.commit_at("Added the library", &["file"], commit_time);
+        let tag_time = git2::Time::new(Utc.ymd(2020, 3, 4).and_hms(12, 0, 0).timestamp(), 0);
+        let tagger = git2::Signature::new("Test", "test@example.com", &tag_time).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag("v1", head.as_object(), &tagger, "v1", false)
+            .unwrap();
+
+        for (source, date) in &[
+            ("commit", Utc.ymd(2020, 1, 2)),
+            ("tag", Utc.ymd(2020, 3, 4)),
+        ] {
+            let mut journal = repo.journal();
+            journal.config.release_date_source = (*source).to_owned();
+            journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+            assert_eq!(journal.parser.result[0].date, *date);
+            assert_eq!(journal.parse_tag("v1").unwrap().date, *date);
+            assert_eq!(journal.releases(), vec![("v1".to_owned(), *date)]);
+        }
+    }
+
+    #[test]
+    fn parse_log_timezone() {
+        let repo = TempRepo::new();
+        let commit_time = Utc.ymd(2020, 1, 2).and_hms(23, 30, 0).timestamp();
+        repo.commit_at("Added the library", &["file"], commit_time);
+        repo.tag_head("v1");
+
+        for (timezone, date) in &[
+            ("utc", Utc.ymd(2020, 1, 2)),
+            ("+02:00", Utc.ymd(2020, 1, 3)),
+            ("-01:00", Utc.ymd(2020, 1, 2)),
+        ] {
+            let mut journal = repo.journal();
+            journal.config.timezone = Timezone::parse(timezone).unwrap();
+            journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+            assert_eq!(journal.parser.result[0].date, *date);
+        }
+    }
+
+    #[test]
+    fn parse_unreleased() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[("v1", &["Added something"]), ("v2", &["Added something"])]);
+        for message in &["Added the unreleased things", "Fixed the unreleased things"] {
+            repo.commit(message, &["file"]);
+        }
+
+        let mut journal = repo.journal();
+        assert_eq!(journal.unreleased_range(), "v2..HEAD");
+        journal.parse_unreleased().unwrap();
+        let unreleased = journal.parser.result.clone();
+        assert_eq!(unreleased.len(), 1);
+        assert_eq!(unreleased[0].commits.len(), 2);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["v2..HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        assert_eq!(journal.parser.result, unreleased);
+    }
+
+    #[test]
+    fn parse_log_colocated_tags() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1.0.0", &["Added the library"]),
+            ("latest", &["Fixed the library"]),
+        ]);
+        repo.tag_head("v2.0.0");
+
+        for (preference, name) in &[("semver", "v2.0.0"), ("merge", "latest, v2.0.0")] {
+            let mut journal = repo.journal();
+            journal.config.tag_preference = (*preference).to_owned();
+            journal.parse_log(&["HEAD"], &ParseOptions::new()).unwrap();
+            let result = &journal.parser.result;
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].name, *name);
+            assert_eq!(result[0].commits.len(), 1);
+        }
+    }
+
+    #[test]
+    fn parse_tag_between_tags() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1", &["Added the library", "Fixed the library"]),
+            (
+                "v2",
+                &["Added the tests", "Fixed the tests", "Removed the bugs"],
+            ),
+        ]);
+        repo.commit("Added the unreleased things", &["file"]);
+
+        let mut journal = repo.journal();
+        let tag = journal.parse_tag("v1").unwrap();
+        assert_eq!(tag.commits.len(), 2);
+        let tag = journal.parse_tag("v2").unwrap();
+        assert_eq!(tag.name, "v2");
+        assert_eq!(tag.commits.len(), 3);
+    }
+
+    #[test]
+    fn diff_ranges() {
+        let repo = TempRepo::new();
+        repo.commit_releases(&[
+            ("v1", &["Added the library", "Fixed the library"]),
+            (
+                "v2",
+                &["Added the tests", "Fixed the tests", "Removed the bugs"],
+            ),
+        ]);
+
+        let mut journal = repo.journal();
+        let mut texts = journal
+            .diff_ranges("v1", "v2")
+            .into_iter()
+            .map(|commit| commit.summary.text)
+            .collect::<Vec<_>>();
+        texts.sort();
+        assert_eq!(texts, vec!["the bugs", "the tests", "the tests"]);
+        assert!(journal.diff_ranges("v2", "v1").is_empty());
+        assert!(journal.diff_ranges("v1", "not_existing").is_empty());
+        assert!(journal.parser.result.is_empty());
+    }
+
+    #[test]
+    fn parse_log_category_delimiter_pairs() {
+        let repo = TempRepo::new();
+        repo.commit("[Added] the library", &["src/lib.rs"]);
+        repo.commit(
+            "(Fixed) the library\n\n- (Changed) its API",
+            &["src/lib.rs"],
+        );
+        repo.commit("[Removed) mixed delimiters", &["src/lib.rs"]);
+
+        let mut journal = repo.journal();
+        journal.config.category_delimiters = vec![
+            ("[".to_owned(), "]".to_owned()),
+            ("(".to_owned(), ")".to_owned()),
+        ];
+        journal.parser.config = journal.config.clone();
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        // Mixed delimiters are not valid
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 2);
+        let added = commits.iter().find(|c| c.summary.category == "Added");
+        assert_eq!(added.unwrap().summary.text, "the library");
+        let fixed = commits
+            .iter()
+            .find(|c| c.summary.category == "Fixed")
+            .unwrap();
+        assert_eq!(fixed.summary.text, "the library");
+        match fixed.body[0] {
+            parser::BodyElement::List(ref list) => assert_eq!(list[0].category, "Changed"),
+            _ => panic!("No list parsed"),
+        }
+    }
+
+    #[test]
+    fn parse_log_infer_category() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["src/lib.rs"]);
+        repo.commit("Cover the library", &["tests/lib.rs", "tests/a/b.rs"]);
+        repo.commit("Update everything", &["src/lib.rs", "tests/lib.rs"]);
+        repo.commit("Fixed the tests", &["tests/lib.rs"]);
+
+        let mut journal = repo.journal();
+        journal.config.require_category = false;
+        journal
+            .config
+            .infer_category_from_paths
+            .insert("tests/*".to_owned(), "Tested".to_owned());
+        journal.parser.config = journal.config.clone();
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+
+        let mut categories = journal.parser.result[0]
+            .commits
+            .iter()
+            .map(|c| (c.summary.text.as_str(), c.summary.category.as_str()))
+            .collect::<Vec<_>>();
+        categories.sort();
+        assert_eq!(
+            categories,
+            vec![
+                ("Cover the library", "Tested"),
+                ("Update everything", ""),
+                ("the library", "Added"),
+                ("the tests", "Fixed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_log_signature_status() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["src/lib.rs"]);
+
+        // The signature is not verified, so any content will do
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let buffer = repo
+            .commit_create_buffer(
+                &signature,
+                &signature,
+                "Fixed the library",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        let oid = repo
+            .commit_signed(
+                buffer.as_str().unwrap(),
+                "-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----",
+                None,
+            )
+            .unwrap();
+        repo.reference("refs/heads/master", oid, true, "signed")
+            .unwrap();
+        repo.set_head("refs/heads/master").unwrap();
+
+        // Signatures are only extracted if the status will be shown
+        let mut journal = repo.journal();
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert!(journal.parser.result[0].commits.iter().all(|c| !c.signed));
+
+        let mut journal = repo.journal();
+        journal.config.show_signature_status = true;
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 2);
+        for commit in commits {
+            assert_eq!(commit.signed, commit.summary.category == "Fixed");
+            assert_eq!(commit.summary.signed, commit.signed);
+        }
+    }
+
+    #[test]
+    fn parse_log_notes() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["src/lib.rs"]);
+        repo.commit("Fixed the library", &["src/lib.rs"]);
+        let head = repo.head().unwrap().target().unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.note(&signature, &signature, None, head, "Some context\n", false)
+            .unwrap();
+
+        let mut journal = repo.journal();
+        journal.config.show_notes = true;
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        for commit in &journal.parser.result[0].commits {
+            if commit.oid == Some(head) {
+                assert_eq!(commit.note, Some("Some context".to_owned()));
+            } else {
+                assert_eq!(commit.note, None);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_log_files_changed() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["src/lib.rs"]);
+        repo.commit(
+            "Fixed the library",
+            &["src/lib.rs", "src/parser.rs", "README.md"],
+        );
+
+        let mut journal = repo.journal();
+        journal.config.colored_output = false;
+        journal.config.show_files_changed = true;
+        journal.config.max_files_changed = 2;
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits[1].files_changed, vec!["src/lib.rs"]);
+        assert_eq!(
+            commits[0].files_changed,
+            vec!["README.md", "src/lib.rs", "src/parser.rs"]
+        );
+        assert_eq!(
+            commits[0].render(&journal.config, false),
+            "- [Fixed] the library\n    - README.md\n    - src/lib.rs\n    - +1 more"
+        );
+        assert_eq!(
+            commits[0].render(&journal.config, true),
+            "- [Fixed] the library"
+        );
+    }
+
+    #[test]
+    fn parse_log_duplicate_ranges() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&["v1..v2", "v1..v2"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 1);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert_eq!(journal.parser.result[0].commits.len(), 1);
+    }
+
+    #[test]
+    fn parse_and_print_log_7() {
+        let mut journal = GitJournal::new("./tests/test_repo2").unwrap();
+        assert!(journal
+            .parse_log(
+                &["HEAD"],
+                &ParseOptions::new().all(true).path_spec(&["tests"])
+            )
+            .is_ok());
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.prepare("./tests/COMMIT_EDITMSG", None).is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_1", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_3() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_2", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_4() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_4", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_message_conventional() {
+        let repo = TempRepo::new();
+        let path = repo.path.join("COMMIT_EDITMSG");
+        fs::write(&path, "Some message\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.commit_style = "conventional".to_owned();
+        journal.config.categories = vec!["feat".to_owned(), "fix".to_owned()];
+        journal.config.known_scopes = vec!["parser".to_owned(), "cli".to_owned()];
+        journal.config.template_prefix = "".to_owned();
+        assert!(journal.prepare(path, None).is_ok());
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "feat(<scope>): ...\n\n# Add a more detailed description if needed\n\n# - feat\n\
+             # - fix\n\n# Available scopes:\n# - parser\n# - cli\n\n\
+             # The provided commit message:\n# Some message"
+        );
+    }
+
+    #[test]
+    fn prepare_message_branch_template() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library", &["src/lib.rs"]);
+
+        let mut journal = repo.journal();
+        journal.config.branch_templates.insert(
+            "hotfix/*".to_owned(),
+            BranchTemplate {
+                template_prefix: Some("HOTFIX-1".to_owned()),
+                categories: Some(vec!["Fixed".to_owned()]),
+            },
+        );
+        let message = repo.path.join("COMMIT_EDITMSG");
+        let prepare = |journal: &GitJournal| {
+            fs::write(&message, "").unwrap();
+            journal.prepare(message.to_str().unwrap(), None).unwrap();
+            fs::read_to_string(&message).unwrap()
+        };
+
+        // Other branches use the default template
+        assert!(prepare(&journal).starts_with("JIRA-1234 Added ...\n"));
+
+        repo.set_head("refs/heads/hotfix/login").unwrap();
+        assert_eq!(
+            prepare(&journal),
+            "HOTFIX-1 Fixed ...\n\n# Add a more detailed description if needed\n\n\
+             # - Fixed\n\n"
+        );
+    }
+
+    #[test]
+    fn prepare_and_verify_comment_char() {
+        let repo = TempRepo::new();
+        repo.config()
+            .unwrap()
+            .set_str("core.commentChar", ";")
+            .unwrap();
+
+        let journal = repo.journal();
+        assert_eq!(journal.config.comment_char, ";");
+        let message = repo.path.join("COMMIT_EDITMSG");
+        fs::write(&message, "Some message\n\n# Not a comment").unwrap();
+        journal.prepare(message.to_str().unwrap(), None).unwrap();
+        let prepared = fs::read_to_string(&message).unwrap();
+        assert!(prepared.starts_with(
+            "JIRA-1234 Added ...\n\n; Add a more detailed description if needed\n\n; - Added\n"
+        ));
+        assert!(prepared
+            .ends_with("; The provided commit message:\n; Some message\n;\n; # Not a comment"));
+
+        // Only lines with the configured comment char are ignored
+        fs::write(
+            &message,
+            "Added the feature\n\n; - Unknown thing\n\n# - Unknown thing",
+        )
+        .unwrap();
+        let error = journal.verify(message.to_str().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("Unknown thing"));
+        fs::write(&message, "Added the feature\n\n; - Unknown thing").unwrap();
+        assert!(journal.verify(message.to_str().unwrap()).is_ok());
+    }
+
+    fn prepare_temp_message(message: &str, commit_type: Option<&str>) -> String {
+        let repo = TempRepo::new();
+        let path = repo.path.join("COMMIT_EDITMSG");
+        fs::copy(message, &path).unwrap();
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.prepare(path.to_str().unwrap(), commit_type).is_ok());
+        fs::read_to_string(&path).unwrap()
+    }
+
+    #[test]
+    fn prepare_message_paragraphs() {
+        let message = prepare_temp_message("./tests/commit_messages/prepare_5", None);
+        assert!(message.ends_with(
+            "# The provided commit message:\n# Rework the parser\n#\n# The first paragraph\n\
+             # spans two lines.\n#\n#\n# The second paragraph.\n#\n# - A list item"
+        ));
+    }
+
+    #[test]
+    fn prepare_message_source_types() {
+        let merge = "./tests/commit_messages/prepare_merge";
+        let prepared = prepare_temp_message(merge, Some("merge"));
+        assert_eq!(prepared, fs::read_to_string(merge).unwrap());
+
+        let squash = "./tests/commit_messages/prepare_squash";
+        let prepared = prepare_temp_message(squash, Some("squash"));
+        assert_eq!(prepared, fs::read_to_string(squash).unwrap());
+
+        let valid = "./tests/commit_messages/success_1";
+        let prepared = prepare_temp_message(valid, Some("commit"));
+        assert_eq!(prepared, fs::read_to_string(valid).unwrap());
+
+        let prepared = prepare_temp_message(merge, Some("commit"));
+        assert!(prepared.starts_with("JIRA-1234 Added ..."));
+        assert!(prepared.ends_with("# Merge branch 'feature' into master"));
+
+        let prepared = prepare_temp_message(merge, Some("template"));
+        assert!(prepared.starts_with("JIRA-1234 Added ..."));
+    }
+
+    #[test]
+    fn prepare_message_failure_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.prepare("TEST", None).is_err());
+        assert!(journal.prepare("TEST", Some("message")).is_err());
+    }
+
+    #[test]
+    fn prepare_message_failure_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_3", Some("message"))
+            .is_err());
+    }
+
+    #[test]
+    fn install_git_hook() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
+        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
+        assert!(journal.install_git_hook("test", "echo 2\n").is_ok());
+    }
+
+    #[test]
+    fn install_single_hook() {
+        let repo = TempRepo::new();
+
+        let journal = repo.journal();
+        assert!(journal.install_hook(HookKind::CommitMsg).is_ok());
+        let hooks = repo.path.join(".git/hooks");
+        assert_eq!(
+            fs::read_to_string(hooks.join(HookKind::CommitMsg.name())).unwrap(),
+            "#!/usr/bin/env sh\ngit journal v $1\n"
+        );
+        assert!(!hooks.join(HookKind::PrepareCommitMsg.name()).exists());
+        assert!(!repo.path.join(".gitjournal.toml").exists());
+    }
+
+    #[test]
+    fn install_git_hook_hooks_path() {
+        let repo = TempRepo::new();
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "custom/hooks")
+            .unwrap();
+
+        let journal = repo.journal();
+        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
+        assert!(repo.path.join("custom/hooks/test").exists());
+        assert!(!repo.path.join(".git/hooks/test").exists());
+    }
+
+    #[test]
+    fn generate_template_1() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal.generate_template(None).is_ok());
+        assert!(journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .is_ok());
+        assert!(journal.generate_template(None).is_ok());
+    }
+
+    #[test]
+    fn generate_template_stable_order() {
+        let repo = TempRepo::new();
+        repo.commit("Added the zeta feature :zeta:", &["file"]);
+        repo.commit("Added the api :api: :core:", &["file"]);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        let template = repo.path.join("template.toml");
+        journal.generate_template(None).unwrap();
+        let first = fs::read(&template).unwrap();
+        fs::remove_file(&template).unwrap();
+        journal.generate_template(None).unwrap();
+        assert_eq!(fs::read(&template).unwrap(), first);
+
+        let toml: Value = toml::from_slice(&first).unwrap();
+        let tags = toml["tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tag| tag["tag"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["default", "api", "core", "zeta"]);
+    }
+
+    #[test]
+    fn generate_template_keep_existing() {
+        let repo = TempRepo::new();
+        repo.commit("Added the library :library:", &["file"]);
+
+        let mut journal = repo.journal();
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        journal.generate_template(None).unwrap();
+
+        // Customize the template and regenerate it with a new tag
+        let template = repo.path.join("template.toml");
+        let toml = fs::read_to_string(&template).unwrap().replace(
+            "[header]\nonce = false\ntext = \"\"",
+            "[header]\nonce = true\ntext = \"My header\"",
+        );
+        fs::write(
+            &template,
+            toml.replace("name = \"library\"", "name = \"Library\""),
+        )
+        .unwrap();
+        repo.commit("Added the tests :tests:", &["file"]);
+        journal
+            .parse_log(&["HEAD"], &ParseOptions::new().all(true))
+            .unwrap();
+        journal.generate_template(None).unwrap();
+
+        let toml: Value = toml::from_str(&fs::read_to_string(&template).unwrap()).unwrap();
+        assert_eq!(toml["header"]["text"].as_str(), Some("My header"));
+        assert_eq!(toml["header"]["once"].as_bool(), Some(true));
+        let tags = toml["tags"].as_array().unwrap();
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[1]["name"].as_str(), Some("Library"));
+        assert_eq!(tags[2]["tag"].as_str(), Some("tests"));
+
+        let output = repo.path.join(".changelog/template.toml");
+        journal.generate_template(output.to_str()).unwrap();
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn tag_pattern_substring() {
+        let pattern = TagPattern::new("rc");
+        assert!(pattern.is_match("v1.0.0-rc1"));
+        assert!(pattern.is_match("v1.0.0-rc2"));
+        assert!(pattern.is_match("v1.0.0-rc.1"));
+        assert!(!pattern.is_match("v1.0.0"));
+    }
+
+    #[test]
+    fn tag_pattern_regex() {
+        let pattern = TagPattern::new(r"-rc\d+$");
+        assert!(pattern.is_match("v1.0.0-rc1"));
+        assert!(pattern.is_match("v1.0.0-rc2"));
+        assert!(!pattern.is_match("v1.0.0-rc.1"));
+        assert!(!pattern.is_match("v1.0.0"));
+
+        let pattern = TagPattern::new(r"-rc(\.?\d+)?$");
+        assert!(pattern.is_match("v1.0.0-rc1"));
+        assert!(pattern.is_match("v1.0.0-rc.1"));
+        assert!(!pattern.is_match("v1.0.0-rcfoo"));
+    }
+
+    #[test]
+    fn tag_pattern_invalid_regex() {
+        let pattern = TagPattern::new("rc(");
+        assert!(pattern.is_match("v1.0.0-rc(1)"));
+        assert!(!pattern.is_match("v1.0.0-rc1"));
+    }
+
+    #[test]
+    fn release_tag_only_pattern() {
+        let skip = TagPattern::new("rc");
+        let only = TagPattern::new(r"^v\d");
+        assert!(is_release_tag("v1.0.0", &skip, Some(&only)));
+        assert!(is_release_tag("v2", &skip, Some(&only)));
+        assert!(!is_release_tag("v1.0.0-rc1", &skip, Some(&only)));
+        assert!(!is_release_tag("ci-build-42", &skip, Some(&only)));
+        assert!(!is_release_tag("nightly", &skip, Some(&only)));
+        assert!(is_release_tag("ci-build-42", &skip, None));
+        assert!(!is_release_tag("v1.0.0-rc1", &skip, None));
+    }
+
+    #[test]
+    fn path_failure() {
+        assert!(GitJournal::new("/etc/").is_err());
+    }
+}
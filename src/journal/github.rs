@@ -0,0 +1,248 @@
+//! Publishing of the parsed tags as GitHub releases, which requires the
+//! `github` feature and a `curl` executable.
+
+use super::GitJournal;
+use crate::{
+    output::Output,
+    parser::{ParsedTag, Parser},
+};
+use failure::{bail, format_err, Error};
+use log::info;
+use serde_json::{json, Value};
+use std::{
+    io::prelude::*,
+    process::{Command, Stdio},
+};
+
+/// The base URL of the GitHub REST API.
+const GITHUB_API: &str = "https://api.github.com";
+
+impl GitJournal {
+    /// Publishes the markdown of every parsed git tag as the body of the
+    /// corresponding GitHub release of the repository `repo_slug`, like
+    /// `"owner/repo"`. Existing releases will be updated, whereas missing ones
+    /// will be created. Sections without a git tag, like the unreleased
+    /// commits, are skipped. GitHub renders the bodies as markdown, which is
+    /// why the commit text is escaped like within output files.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .parse_log(&["HEAD"], &ParseOptions::new().skip_unreleased(true))
+    ///     .unwrap();
+    /// journal
+    ///     .publish_github_releases("saschagrunert/git-journal", "<token>")
+    ///     .expect("Could not publish the releases.");
+    /// ```
+    ///
+    /// # Errors
+    /// When `curl` is not available, the API rate limit is exceeded or any
+    /// other request fails.
+    pub fn publish_github_releases(&self, repo_slug: &str, token: &str) -> Result<(), Error> {
+        let releases = format!("{}/repos/{}/releases", GITHUB_API, repo_slug);
+        for tag in &self.parser.result {
+            if !self.tags.iter().any(|(_, name, _)| *name == tag.name) {
+                info!("Skipping '{}', which is no git tag.", tag.name);
+                continue;
+            }
+            let body = self.release_body(tag)?;
+            let response = request(
+                "GET",
+                &format!("{}/tags/{}", releases, tag.name),
+                token,
+                None,
+            )?;
+            if response.status == 404 {
+                let release = json!({ "tag_name": tag.name, "name": tag.name, "body": body });
+                request("POST", &releases, token, Some(&release))?.ok()?;
+                info!("Created the GitHub release '{}'.", tag.name);
+            } else {
+                let id = response.ok()?["id"]
+                    .as_u64()
+                    .ok_or_else(|| format_err!("No id of the GitHub release '{}'", tag.name))?;
+                let release = json!({ "body": body });
+                request(
+                    "PATCH",
+                    &format!("{}/{}", releases, id),
+                    token,
+                    Some(&release),
+                )?
+                .ok()?;
+                info!("Updated the GitHub release '{}'.", tag.name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the markdown of a single tag without its header, since the
+    /// release has a title on its own. The commit text is escaped unless
+    /// `escape_markdown` is disabled.
+    fn release_body(&self, tag: &ParsedTag) -> Result<String, Error> {
+        let mut config = self.config.clone();
+        config.title = None;
+        let mut parser = Parser::new(config);
+        parser.result = vec![tag.clone()];
+        let mut writer = Output::new_buffer();
+        parser.print_to(false, None, "markdown", true, &mut writer)?;
+        let markdown = writer
+            .as_str()
+            .ok_or_else(|| format_err!("The printed release is not valid UTF-8."))?;
+        Ok(markdown
+            .trim_start()
+            .split_once('\n')
+            .map(|(_, body)| body)
+            .unwrap_or_default()
+            .trim()
+            .to_owned())
+    }
+}
+
+/// The status and JSON body of a GitHub API response.
+struct Response {
+    status: u32,
+    json: Value,
+}
+
+impl Response {
+    /// Parses the output of `curl`, where the status code is written to the
+    /// last line.
+    fn parse(output: &str) -> Result<Self, Error> {
+        let (body, status) = output.rsplit_once('\n').unwrap_or(("", output));
+        Ok(Self {
+            status: status.trim().parse()?,
+            json: if body.trim().is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_str(body)?
+            },
+        })
+    }
+
+    /// Returns the JSON body of a successful response, where exceeded rate
+    /// limits are reported as such.
+    fn ok(self) -> Result<Value, Error> {
+        let message = self.json["message"].as_str().unwrap_or_default();
+        match self.status {
+            200..=299 => Ok(self.json),
+            403 | 429 if message.to_lowercase().contains("rate limit") => bail!(
+                "GitHub API rate limit exceeded, please retry later: {}",
+                message
+            ),
+            status => bail!(
+                "GitHub API request failed with status {}: {}",
+                status,
+                message
+            ),
+        }
+    }
+}
+
+/// Sends a request to the GitHub API via `curl`. The options are passed via
+/// stdin to keep the token out of the process list.
+fn request(method: &str, url: &str, token: &str, body: Option<&Value>) -> Result<Response, Error> {
+    let mut options = format!(
+        "url = \"{}\"\nrequest = \"{}\"\nheader = \"Authorization: token {}\"\n\
+         header = \"Accept: application/vnd.github.v3+json\"\nheader = \"User-Agent: git-journal\"\n",
+        escape(url),
+        method,
+        escape(token)
+    );
+    if let Some(body) = body {
+        options += &format!("data-binary = \"{}\"\n", escape(&body.to_string()));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--config", "-"])
+        .args(["--write-out", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format_err!("Could not run curl: {}", e))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| format_err!("Could not write to curl"))?
+        .write_all(options.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "GitHub API request failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Response::parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Escapes a value for a quoted string within the `curl` options.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{offset::Utc, TimeZone};
+
+    #[test]
+    fn response_status() {
+        let response = Response::parse("{\"id\": 1}\n200").unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.ok().unwrap()["id"], 1);
+
+        let response = Response::parse("{\"message\": \"Not Found\"}\n404").unwrap();
+        assert_eq!(
+            response.ok().unwrap_err().to_string(),
+            "GitHub API request failed with status 404: Not Found"
+        );
+
+        let response =
+            Response::parse("{\"message\": \"API rate limit exceeded for user.\"}\n403").unwrap();
+        assert!(response
+            .ok()
+            .unwrap_err()
+            .to_string()
+            .starts_with("GitHub API rate limit exceeded"));
+
+        assert!(Response::parse("\n204").unwrap().ok().is_ok());
+    }
+
+    #[test]
+    fn escape_options() {
+        assert_eq!(
+            escape(&json!({ "body": "- [Added] \"quotes\"\n" }).to_string()),
+            r#"{\"body\":\"- [Added] \\\"quotes\\\"\\n\"}"#
+        );
+    }
+
+    #[test]
+    fn release_body() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.colored_output = false;
+        journal.config.title = Some("Changelog".to_owned());
+        let commit = journal
+            .parser
+            .parse_commit_message("Added the * feature\n\n- [Fixed] the bug", None)
+            .unwrap();
+        let tag = ParsedTag {
+            name: "v1.0.0".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![commit],
+            message_ids: vec![],
+        };
+        assert_eq!(
+            journal.release_body(&tag).unwrap(),
+            "- [Added] the \\* feature\n    - [Fixed] the bug"
+        );
+
+        journal.config.escape_markdown = Some(false);
+        assert_eq!(
+            journal.release_body(&tag).unwrap(),
+            "- [Added] the * feature\n    - [Fixed] the bug"
+        );
+    }
+}
@@ -1,8 +1,106 @@
-use clap::{crate_version, load_yaml, App, Shell};
-use failure::{bail, format_err, Error};
-use gitjournal::GitJournal;
+use clap::{crate_version, load_yaml, App, ArgMatches, Shell};
+use failure::{format_err, Error, Fail};
+use gitjournal::{GitJournal, ParseOptions};
 use log::info;
-use std::{env, fs};
+use std::{env, fmt, fs, io, io::Read, path::Path, process};
+
+/// Exit code for all failures without a more specific one
+const EXIT_FAILURE: i32 = 1;
+
+/// Exit code if the commit message verification failed
+const EXIT_VERIFICATION: i32 = 2;
+
+/// Exit code if the configuration or a template could not be read or written
+const EXIT_CONFIG: i32 = 3;
+
+/// Exit code if a git operation failed, like opening the repository
+const EXIT_GIT: i32 = 4;
+
+/// A commit message which did not pass the verification.
+#[derive(Debug)]
+struct InvalidCommitMessage(String);
+
+impl fmt::Display for InvalidCommitMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Commit message invalid")?;
+        if !self.0.is_empty() {
+            write!(f, " {}", self.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl Fail for InvalidCommitMessage {}
+
+/// Marks a failed verification as an `InvalidCommitMessage`, where other
+/// failures like an unreadable message keep their own exit code.
+fn verification_error(error: Error) -> Error {
+    if exit_code(&error) != EXIT_FAILURE || error.downcast_ref::<io::Error>().is_some() {
+        error
+    } else {
+        InvalidCommitMessage(error.to_string()).into()
+    }
+}
+
+/// Returns the process exit code for the first known failure within the
+/// chain of causes.
+fn exit_code(error: &Error) -> i32 {
+    for cause in error.iter_chain() {
+        if cause.downcast_ref::<InvalidCommitMessage>().is_some() {
+            return EXIT_VERIFICATION;
+        }
+        if cause.downcast_ref::<toml::de::Error>().is_some()
+            || cause.downcast_ref::<toml::ser::Error>().is_some()
+        {
+            return EXIT_CONFIG;
+        }
+        if cause.downcast_ref::<git2::Error>().is_some() {
+            return EXIT_GIT;
+        }
+    }
+    EXIT_FAILURE
+}
+
+/// Reads the revision range from a file for `@<file>` or from stdin for `-`,
+/// otherwise it is returned as it is. Revisions like `@{u}` stay untouched as
+/// long as no such file exists.
+fn read_revision_range(value: &str) -> Result<String, Error> {
+    if value == "-" {
+        let mut range = String::new();
+        io::stdin().read_to_string(&mut range)?;
+        return Ok(range.trim().to_owned());
+    }
+    match value.strip_prefix('@') {
+        Some(file) if Path::new(file).is_file() => Ok(fs::read_to_string(file)?.trim().to_owned()),
+        _ => Ok(value.to_owned()),
+    }
+}
+
+/// Returns whether all commits should be parsed and the number of tags until
+/// the parser stops. The `parse_all` configuration only applies if no tags
+/// count is given, whereas `--all` parses everything without any tags count.
+fn tags_limit(matches: &ArgMatches, parse_all: bool) -> Result<(bool, u32), Error> {
+    let tags_count_given = matches.occurrences_of("tags_count") > 0;
+    let all = matches.is_present("all") || (parse_all && !tags_count_given);
+    if all && !tags_count_given {
+        return Ok((true, 0));
+    }
+    let max_tags = matches
+        .value_of("tags_count")
+        .ok_or_else(|| format_err!("No CLI 'tags_count' provided"))?
+        .parse::<u32>()?;
+    Ok((all, max_tags))
+}
+
+/// Decides if the unreleased entries are skipped, where the given CLI flags
+/// take precedence over the configured value.
+fn skip_unreleased(matches: &ArgMatches, configured: bool) -> bool {
+    if matches.is_present("unreleased") || matches.is_present("no_skip_unreleased") {
+        false
+    } else {
+        matches.is_present("skip_unreleased") || configured
+    }
+}
 
 fn is_program_in_path(program: &str) -> bool {
     if let Ok(path) = env::var("PATH") {
@@ -16,7 +114,14 @@ fn is_program_in_path(program: &str) -> bool {
     false
 }
 
-fn main() -> Result<(), Error> {
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("Error: {}", error);
+        process::exit(exit_code(&error));
+    }
+}
+
+fn run() -> Result<(), Error> {
     // Load the CLI parameters from the yaml file
     let yaml = load_yaml!("cli.yaml");
     let mut app = App::from_yaml(yaml).version(crate_version!());
@@ -40,8 +145,35 @@ fn main() -> Result<(), Error> {
                     sub_matches.value_of("type"),
                 ) {
                     Ok(()) => info!("Commit message prepared."),
-                    Err(error) => bail!("Commit message preparation failed {}", error),
+                    Err(error) => {
+                        let message = format!("Commit message preparation failed {}", error);
+                        return Err(error.context(message).into());
+                    }
+                }
+            }
+        }
+        Some("parse") => {
+            // Print the structured parsing result for external tools
+            if let Some(sub_matches) = matches.subcommand_matches("parse") {
+                let revision_range = read_revision_range(
+                    sub_matches
+                        .value_of("revision_range")
+                        .ok_or_else(|| format_err!("No CLI 'revision_range' provided"))?,
+                )?;
+                let revision_ranges: Vec<&str> = revision_range.split(',').collect();
+                let tag_skip_pattern = sub_matches
+                    .value_of("tag_skip_pattern")
+                    .ok_or_else(|| format_err!("No CLI 'task_skip_pattern' provided"))?;
+                let (all, max_tags) = tags_limit(sub_matches, journal.config.parse_all)?;
+                let options = ParseOptions::new()
+                    .tag_skip_pattern(tag_skip_pattern)
+                    .all(all)
+                    .max_tags_count(max_tags);
+                if let Err(error) = journal.parse_log(&revision_ranges, &options) {
+                    let message = format!("Log parsing error {}", error);
+                    return Err(error.context(message).into());
                 }
+                println!("{}", serde_json::to_string_pretty(journal.parsed_tags())?);
             }
         }
         Some("setup") => {
@@ -65,58 +197,158 @@ fn main() -> Result<(), Error> {
         Some("verify") => {
             // Verify a commit message
             if let Some(sub_matches) = matches.subcommand_matches("verify") {
-                match journal.verify(
-                    sub_matches
-                        .value_of("message")
-                        .ok_or_else(|| format_err!("No CLI 'message' provided"))?,
-                ) {
-                    Ok(()) => info!("Commit message valid."),
-                    Err(error) => bail!("Commit message invalid {}", &error),
+                if sub_matches.is_present("unreleased") {
+                    journal.verify_unreleased().map_err(verification_error)?;
+                    info!("All commit messages since the latest release are valid.");
+                    return Ok(());
+                }
+                let message = sub_matches
+                    .value_of("message")
+                    .ok_or_else(|| format_err!("No CLI 'message' provided"))?;
+                if sub_matches.value_of("format") == Some("json") {
+                    let report = journal.verify_report(message)?;
+                    println!("{}", serde_json::to_string(&report)?);
+                    if !report.valid {
+                        return Err(InvalidCommitMessage(String::new()).into());
+                    }
+                } else {
+                    let result = if journal.config.autofix {
+                        journal.autofix(message)
+                    } else {
+                        journal.verify(message)
+                    };
+                    result.map_err(verification_error)?;
+                    info!("Commit message valid.");
                 }
             }
         }
         _ => {
             // Get all values of the given CLI parameters with default values
-            let revision_range = matches
-                .value_of("revision_range")
-                .ok_or_else(|| format_err!("No CLI 'revision_range' provided"))?;
+            let revision_range = if matches.is_present("unreleased") {
+                journal.unreleased_range()
+            } else if let Some(branch) = matches.value_of("branch") {
+                journal.branch_range(branch)?
+            } else {
+                read_revision_range(
+                    matches
+                        .value_of("revision_range")
+                        .ok_or_else(|| format_err!("No CLI 'revision_range' provided"))?,
+                )?
+            };
+            let revision_ranges: Vec<&str> = revision_range.split(',').collect();
             let tag_skip_pattern = matches
                 .value_of("tag_skip_pattern")
                 .ok_or_else(|| format_err!("No CLI 'task_skip_pattern' provided"))?;
-            let tags_count = matches
-                .value_of("tags_count")
-                .ok_or_else(|| format_err!("No CLI 'tags_count' provided"))?;
-            let max_tags = tags_count.parse::<u32>()?;
-            let ignore_tags: Option<Vec<&str>> = matches
-                .value_of("ignore_tags")
-                .map(|s| s.split(',').collect());
-            let path_spec: Option<Vec<&str>> =
-                matches.values_of("PATH_SPEC").map(|ps| ps.collect());
+            let (all, max_tags) = tags_limit(&matches, journal.config.parse_all)?;
+            let mut options = ParseOptions::new()
+                .tag_skip_pattern(tag_skip_pattern)
+                .all(all)
+                .max_tags_count(max_tags)
+                .skip_unreleased(skip_unreleased(&matches, journal.config.skip_unreleased));
+            if let Some(only_tags_pattern) = matches.value_of("only_tags") {
+                options = options.only_tags_pattern(only_tags_pattern);
+            }
+            if let Some(ignore_tags) = matches.value_of("ignore_tags") {
+                options = options.ignore_tags(&ignore_tags.split(',').collect::<Vec<_>>());
+            }
+            if let Some(path_spec) = matches.values_of("PATH_SPEC") {
+                options = options.path_spec(&path_spec.collect::<Vec<_>>());
+            }
+            if let Some(limit) = matches.value_of("limit") {
+                options = options.limit(limit.parse::<usize>()?);
+            }
+
+            if let Some(color) = matches.value_of("color") {
+                journal.config.color = color.to_owned();
+            }
+            if let Some(title) = matches.value_of("title") {
+                let mut config = journal.config.clone();
+                config.title = Some(title.to_owned());
+                journal.set_config(config);
+            }
+            if matches.is_present("append") {
+                journal.config.append_output = true;
+            } else if matches.is_present("overwrite") {
+                journal.config.append_output = false;
+            }
+
+            // Report the progress of the revision walk on stderr if requested
+            let progress = matches.is_present("progress");
+            if progress {
+                journal.set_progress(|current, total| {
+                    eprint!("\rParsing commits: {}/{}", current, total)
+                });
+            }
 
             // Parse the log
-            if let Err(error) = journal.parse_log(
-                revision_range,
-                tag_skip_pattern,
-                max_tags,
-                matches.is_present("all"),
-                matches.is_present("skip_unreleased"),
-                ignore_tags,
-                path_spec.as_ref(),
-            ) {
-                bail!("Log parsing error {}", &error);
+            let result = journal.parse_log(&revision_ranges, &options);
+            if progress {
+                eprintln!();
+            }
+            if let Err(error) = result {
+                let message = format!("Log parsing error {}", error);
+                return Err(error.context(message).into());
             }
 
             // Generate the template or print the log
             if matches.is_present("generate") {
-                journal.generate_template()?;
+                journal.generate_template(matches.value_of("generate_output"))?;
             } else {
-                journal.print_log(
-                    matches.is_present("short"),
-                    matches.value_of("template"),
-                    matches.value_of("output"),
-                )?;
+                let format = if matches.is_present("digest") {
+                    Some("digest")
+                } else {
+                    matches.value_of("output_format")
+                };
+                match matches.value_of("output") {
+                    Some(output) if matches.is_present("check") => journal.check_log(
+                        matches.is_present("short"),
+                        matches.value_of("template"),
+                        output,
+                        format,
+                    )?,
+                    output => journal.print_log_with_format(
+                        matches.is_present("short"),
+                        matches.value_of("template"),
+                        output,
+                        format,
+                    )?,
+                }
             }
         }
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter;
+
+    #[test]
+    fn revision_range_from_file() {
+        let path = env::temp_dir().join(format!("gitjournal_revision_range_{}", process::id()));
+        fs::write(&path, "v1..v2,v3..HEAD\n").unwrap();
+        let range = read_revision_range(&format!("@{}", path.display()));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(range.unwrap(), "v1..v2,v3..HEAD");
+        assert_eq!(read_revision_range("v1..HEAD").unwrap(), "v1..HEAD");
+        assert_eq!(read_revision_range("@{u}..HEAD").unwrap(), "@{u}..HEAD");
+    }
+
+    #[test]
+    fn skip_unreleased_override() {
+        let yaml = load_yaml!("cli.yaml");
+        let app = App::from_yaml(yaml);
+        let skip = |args: &[&str], configured| {
+            let matches = app
+                .clone()
+                .get_matches_from(iter::once(&"git-journal").chain(args));
+            skip_unreleased(&matches, configured)
+        };
+        assert!(!skip(&[], false));
+        assert!(skip(&[], true));
+        assert!(skip(&["-u"], false));
+        assert!(!skip(&["--no-skip-unreleased"], true));
+        assert!(!skip(&["--unreleased"], true));
+    }
+}
@@ -1,4 +1,5 @@
 use failure::Error;
+use std::io::IsTerminal;
 use term::color::Color;
 
 /// An abstraction over all outputs
@@ -17,13 +18,27 @@ impl Output {
         Output::Buffer(Vec::new())
     }
 
-    /// Creates an output that writes into the terminal
+    /// Creates an output that writes into the terminal, where colors are only
+    /// used if the standard output is a TTY
     pub fn new_terminal() -> Self {
-        if let Some(terminal) = term::stdout() {
-            Self::Terminal(terminal)
-        } else {
-            Self::TerminalFallback(std::io::stdout())
+        Self::new_terminal_with_color("auto")
+    }
+
+    /// Creates an output that writes into the terminal with the given color
+    /// mode: `"always"`, `"never"` or `"auto"`, which detects if the standard
+    /// output is a TTY
+    pub fn new_terminal_with_color(color: &str) -> Self {
+        if use_color(color, std::io::stdout().is_terminal()) {
+            if let Some(terminal) = term::stdout() {
+                return Self::Terminal(terminal);
+            }
         }
+        Self::TerminalFallback(std::io::stdout())
+    }
+
+    /// Tests if the Output is able to write colors
+    pub fn is_colored(&self) -> bool {
+        matches!(self, Self::Terminal(_))
     }
 
     /// Tests if the Output is to a buffer
@@ -35,6 +50,36 @@ impl Output {
         }
     }
 
+    /// Returns the written bytes if the Output is to a buffer
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        if let Self::Buffer(vec) = self {
+            Some(vec)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the written text if the Output is to a buffer which contains
+    /// valid UTF-8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{Config, Output, Parser};
+    ///
+    /// let parser = Parser::new(Config::new());
+    /// let mut output = Output::new_buffer();
+    /// parser.print(true, None, "markdown", &mut output).unwrap();
+    /// assert_eq!(output.as_str(), Some(""));
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        if let Self::Buffer(vec) = self {
+            std::str::from_utf8(vec).ok()
+        } else {
+            None
+        }
+    }
+
     /// Sets the foreground color for the terminal
     pub fn fg(&mut self, color: Color) -> Result<(), Error> {
         if let Self::Terminal(t) = self {
@@ -52,6 +97,16 @@ impl Output {
     }
 }
 
+/// Decides if colors should be used for the color mode, where every unknown
+/// mode behaves like `"auto"`.
+fn use_color(color: &str, is_tty: bool) -> bool {
+    match color {
+        "always" => true,
+        "never" => false,
+        _ => is_tty,
+    }
+}
+
 /// Implement Write for `Output` by forwarding to the underlying Writers
 impl std::io::Write for Output {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
@@ -94,3 +149,32 @@ impl std::io::Write for Output {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn buffer_contents() {
+        let mut output = Output::new_buffer();
+        write!(output, "Changelog").unwrap();
+        assert_eq!(output.as_str(), Some("Changelog"));
+        assert_eq!(output.into_bytes(), Some(b"Changelog".to_vec()));
+
+        let mut output = Output::new_buffer();
+        output.write_all(&[0xff]).unwrap();
+        assert_eq!(output.as_str(), None);
+        assert!(Output::new_terminal().into_bytes().is_none());
+    }
+
+    #[test]
+    fn color_detection() {
+        assert!(!use_color("auto", false));
+        assert!(use_color("auto", true));
+        assert!(use_color("always", false));
+        assert!(!use_color("never", true));
+        assert!(!Output::new_terminal_with_color("never").is_colored());
+        assert!(!Output::new_buffer().is_colored());
+    }
+}
@@ -1,22 +1,65 @@
-use crate::config::Config;
+use crate::config::{Config, FooterPattern};
 use crate::output::Output;
 use chrono::{offset::Utc, Date, Datelike};
-use failure::{bail, format_err, Error};
-use git2::Oid;
+use failure::{format_err, Error, Fail};
 use lazy_static::lazy_static;
 use nom::combinator::map_res;
 use nom::{
-    bytes::streaming::tag,
-    character::streaming::{alpha1, char, digit1, space0, space1},
+    bytes::streaming::{tag, take_till1},
+    character::streaming::{alpha1, char, digit1, one_of, space0, space1},
     combinator::{map, opt, rest},
     regexp::bytes::re_find,
-    sequence::separated_pair,
+    sequence::{delimited, preceded, separated_pair},
     IResult,
 };
+use rayon::prelude::*;
 use regex::{bytes, Regex, RegexBuilder};
-use std::{collections::BTreeMap, fs::File, io::prelude::*, iter, str};
+use serde::Serializer;
+use serde_derive::Serialize;
+use std::{
+    borrow::Cow, cmp::Reverse, collections::BTreeMap, fmt, fs::File, io::prelude::*, iter, mem, str,
+};
 use toml::{self, Value};
 
+#[cfg(feature = "git")]
+pub use git2::Oid;
+
+/// The id of a git object, which keeps the parsing API the same without the
+/// `git` feature.
+#[cfg(not(feature = "git"))]
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Oid([u8; 20]);
+
+#[cfg(not(feature = "git"))]
+impl Oid {
+    /// Parses a hex formatted object id, where shorter ones are padded with
+    /// zeros.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, Error> {
+        if s.len() > 40 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format_err!("Invalid object id '{}'", s));
+        }
+        let hex = format!("{:0<40}", s);
+        let mut bytes = [0; 20];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)?;
+        }
+        Ok(Oid(bytes))
+    }
+}
+
+#[cfg(not(feature = "git"))]
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex = self
+            .0
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        f.pad(&hex)
+    }
+}
+
 pub static TOML_DEFAULT_KEY: &str = "default";
 pub static TOML_FOOTERS_KEY: &str = "footers";
 pub static TOML_NAME_KEY: &str = "name";
@@ -26,6 +69,7 @@ pub static TOML_TEXT_KEY: &str = "text";
 pub static TOML_ONCE_KEY: &str = "once";
 pub static TOML_HEADER_KEY: &str = "header";
 pub static TOML_FOOTER_KEY: &str = "footer";
+pub static TOML_INCLUDE_TIMESTAMP_KEY: &str = "include_timestamp";
 
 #[derive(PartialEq)]
 pub enum Printed {
@@ -33,6 +77,14 @@ pub enum Printed {
     Something,
 }
 
+/// The color closures of `Print::print`, which are called in front of the
+/// category, in front of the text and at the end of an element.
+struct PrintColors<'a, F, G, H> {
+    category: &'a F,
+    text: &'a G,
+    reset: &'a H,
+}
+
 pub trait Print {
     fn print<T: Write, F, G, H>(
         &self,
@@ -59,11 +111,11 @@ pub trait Print {
             config,
             tag,
             &|t| {
-                t.fg(term::color::BRIGHT_BLUE)?;
+                t.fg(config.colors.category)?;
                 Ok(())
             },
             &|t| {
-                t.fg(term::color::WHITE)?;
+                t.fg(config.colors.text)?;
                 Ok(())
             },
             &|t| {
@@ -105,15 +157,100 @@ pub trait Tags {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// The parsed commits of a single git tag, which is a release.
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct ParsedTag {
+    /// The name of the tag, or "Unreleased" for commits without any tag
     pub name: String,
+
+    /// The date of the tagged commit
+    #[serde(serialize_with = "serialize_date")]
     pub date: Date<Utc>,
+
+    /// All parsed commits belonging to the tag
     pub commits: Vec<ParsedCommit>,
+
+    /// The indexes of the commits during the parsing
     pub message_ids: Vec<usize>,
 }
 
 impl ParsedTag {
+    /// The part of the version header after the tag name, like the date.
+    fn header_suffix(&self, config: &Config) -> String {
+        let mut suffix = format!(
+            "({}-{:02}-{:02})",
+            self.date.year(),
+            self.date.month(),
+            self.date.day()
+        );
+        if config.show_commit_count {
+            let count = self.printed_commits(config).count();
+            suffix += &format!(" ({} commit{})", count, if count == 1 { "" } else { "s" });
+        }
+        suffix + ":"
+    }
+
+    /// The commits which will be printed, without the excluded ones.
+    fn printed_commits<'a>(&'a self, config: &'a Config) -> impl Iterator<Item = &'a ParsedCommit> {
+        self.commits
+            .iter()
+            .filter(move |c| !c.summary.is_excluded(config))
+    }
+
+    /// Prints a single line with the number of commits per category, like
+    /// `v1.0.0 (2020-01-02): 3 commits (2 Added, 1 Fixed)`.
+    fn print_digest(&self, writer: &mut Output, config: &Config) -> Result<(), Error> {
+        // The categories are ordered like the configured ones
+        let mut counts: Vec<(&str, usize)> = config
+            .categories
+            .iter()
+            .map(|category| (category.as_str(), 0))
+            .collect();
+        let mut count = 0;
+        for commit in self.printed_commits(config) {
+            count += 1;
+            let category = commit.summary.category.as_str();
+            match counts.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((category, 1)),
+            }
+        }
+        let categories = counts
+            .into_iter()
+            .filter(|&(_, n)| n > 0)
+            .map(|(category, n)| {
+                let name = if category.is_empty() {
+                    "Other"
+                } else {
+                    category_heading(category, config)
+                };
+                format!("{} {}", n, name)
+            })
+            .collect::<Vec<_>>();
+        writeln!(
+            writer,
+            "{} ({}): {} commit{} ({})",
+            self.name,
+            self.date.format("%Y-%m-%d"),
+            count,
+            if count == 1 { "" } else { "s" },
+            categories.join(", ")
+        )?;
+        Ok(())
+    }
+
+    /// Sorts the commits according to the `sort_by` configuration.
+    pub fn sort_commits(&mut self, config: &Config) {
+        match config.sort_by.as_str() {
+            "date" if config.sort_direction == "ascending" => self.commits.sort_by_key(|c| c.time),
+            "date" => self.commits.sort_by_key(|c| Reverse(c.time)),
+            "name" => self.commits.sort_by(|l, r| {
+                (&l.summary.category, &l.summary.text).cmp(&(&r.summary.category, &r.summary.text))
+            }),
+            _ => {}
+        }
+    }
+
     fn print<T: Write, F, G, H>(
         &self,
         t: &mut T,
@@ -127,6 +264,11 @@ impl ParsedTag {
         G: Fn(&mut T) -> Result<(), Error>,
         H: Fn(&mut T) -> Result<(), Error>,
     {
+        if config.document_format == "rst" {
+            let header = format!("{} {}", self.name, self.header_suffix(config));
+            write!(t, "\n{}", section_header(&header, 1, config))?;
+            return Ok(Printed::Something);
+        }
         if config.colored_output {
             c1(t)?;
         }
@@ -134,13 +276,7 @@ impl ParsedTag {
         if config.colored_output {
             c2(t)?;
         }
-        write!(
-            t,
-            "({}-{:02}-{:02}):",
-            self.date.year(),
-            self.date.month(),
-            self.date.day()
-        )?;
+        write!(t, "{}", self.header_suffix(config))?;
         if config.colored_output {
             c3(t)?;
         }
@@ -152,11 +288,11 @@ impl ParsedTag {
             &mut t,
             config,
             &|t| {
-                t.fg(term::color::GREEN)?;
+                t.fg(config.colors.tag)?;
                 Ok(())
             },
             &|t| {
-                t.fg(term::color::YELLOW)?;
+                t.fg(config.colors.date)?;
                 Ok(())
             },
             &|t| {
@@ -172,16 +308,10 @@ impl ParsedTag {
         writer: &mut Output,
         compact: bool,
         config: &Config,
-        template: Option<&str>,
+        template: Option<&Value>,
         index_len: (usize, usize),
     ) -> Result<(), Error> {
-        if let Some(template) = template {
-            // Try to parse the template
-            let mut file = File::open(template)?;
-            let mut toml_string = String::new();
-            file.read_to_string(&mut toml_string)?;
-            let toml: Value = toml::from_str(&toml_string)?;
-
+        if let Some(toml) = template {
             // Print header in template if exists
             if let Some(&Value::Table(ref header_table)) = toml.get(TOML_HEADER_KEY) {
                 let mut print_once = false;
@@ -197,6 +327,7 @@ impl ParsedTag {
 
             // Print the tags
             self.print_default(writer, config)?;
+            self.print_security_commits(writer, config)?;
 
             // Print commits
             if let Some(main_table) = toml.as_table() {
@@ -209,17 +340,33 @@ impl ParsedTag {
                 if let Some(&Value::Boolean(ref once)) = footer_table.get(TOML_ONCE_KEY) {
                     print_once = *once;
                 }
-                if let Some(&Value::String(ref footer)) = footer_table.get(TOML_TEXT_KEY) {
-                    if (index_len.0 == index_len.1 - 1 || !print_once) && !footer.is_empty() {
-                        writeln!(writer, "\n{}", footer)?;
+                let mut footer = match footer_table.get(TOML_TEXT_KEY) {
+                    Some(&Value::String(ref footer)) => footer.clone(),
+                    _ => String::new(),
+                };
+                if let Some(&Value::Boolean(true)) = footer_table.get(TOML_INCLUDE_TIMESTAMP_KEY) {
+                    if !footer.is_empty() {
+                        footer = format!("{} ", footer.trim_end());
                     }
+                    footer += &Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
                 }
+                if (index_len.0 == index_len.1 - 1 || !print_once) && !footer.is_empty() {
+                    writeln!(writer, "\n{}", footer)?;
+                }
+            }
+        } else if config.group_by_category && !compact {
+            self.print_default(writer, config)?;
+            self.print_security_commits(writer, config)?;
+            self.print_commits_by_category(writer, config)?;
+            if config.enable_footers {
+                self.print_footers(writer, None, config)?;
             }
         } else {
             self.print_default(writer, config)?;
+            self.print_security_commits(writer, config)?;
 
             for commit in &self.commits {
-                if compact {
+                if commit.is_compact(compact, config) {
                     commit.summary.print_default(writer, config, None)?;
                 } else {
                     commit.print_default(writer, config, None)?;
@@ -234,6 +381,88 @@ impl ParsedTag {
         Ok(())
     }
 
+    fn print_security_commits(&self, writer: &mut Output, config: &Config) -> Result<(), Error> {
+        if !config.highlight_security {
+            return Ok(());
+        }
+
+        // Collect all commits containing the security footer
+        let commits = self
+            .commits
+            .iter()
+            .filter_map(|commit| {
+                let values = commit
+                    .footer
+                    .iter()
+                    .filter(|footer| footer.key == config.security_footer_key)
+                    .map(|footer| footer.value.as_str())
+                    .collect::<Vec<_>>();
+                if values.is_empty() {
+                    None
+                } else {
+                    Some((commit, values.join(", ")))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !commits.is_empty() {
+            if config.colored_output {
+                writer.fg(config.colors.header)?;
+            }
+            write!(writer, "\n{}", section_header("Security", 2, config))?;
+            writer.reset()?;
+            for (commit, values) in commits {
+                commit.summary.print_default(writer, config, None)?;
+                write!(writer, " ({})", values)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Prints the commits in sections per category, which are ordered like
+    /// the configured categories. Commits without a category come last.
+    fn print_commits_by_category(&self, writer: &mut Output, config: &Config) -> Result<(), Error> {
+        let mut categories = config
+            .categories
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for commit in &self.commits {
+            let category = commit.summary.category.as_str();
+            if !category.is_empty() && !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        categories.push("");
+
+        for category in categories {
+            let commits = self
+                .commits
+                .iter()
+                .filter(|c| c.summary.category == category && !c.summary.is_excluded(config))
+                .collect::<Vec<_>>();
+            if commits.is_empty() {
+                continue;
+            }
+            if config.colored_output {
+                writer.fg(config.colors.header)?;
+            }
+            let name = if category.is_empty() {
+                "Other"
+            } else {
+                category_heading(category, config)
+            };
+            write!(writer, "\n{}", section_header(name, 2, config))?;
+            writer.reset()?;
+            for commit in commits {
+                commit.print_default(writer, config, None)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
     fn print_commits_in_table(
         &self,
         writer: &mut Output,
@@ -254,7 +483,6 @@ impl ParsedTag {
             }
         }
 
-        let header_lvl: String = iter::repeat('#').take(*level).collect();
         let tag = match table.get(TOML_TAG) {
             Some(t) => t.as_str().unwrap_or(""),
             None => return Ok(()),
@@ -264,47 +492,44 @@ impl ParsedTag {
             None => tag,
         };
 
-        if (compact
-            && ((self
-                .commits
-                .iter()
-                .filter(|c| c.summary.contains_tag(Some(tag)))
-                .count()
-                > 0
-                && !config.excluded_commit_tags.contains(&tag.to_owned()))
-                || (tag == TOML_DEFAULT_KEY
-                    && self
-                        .commits
-                        .iter()
-                        .filter(|c| c.summary.contains_untagged_elements())
-                        .count()
-                        > 0)))
-            || (!compact
-                && ((self
-                    .commits
-                    .iter()
-                    .filter(|c| c.contains_tag(Some(tag)))
-                    .count()
-                    > 0
-                    && !config.excluded_commit_tags.contains(&tag.to_owned()))
-                    || (tag == TOML_DEFAULT_KEY
-                        && self
-                            .commits
-                            .iter()
-                            .filter(|c| c.contains_untagged_elements())
-                            .count()
-                            > 0)))
-        {
-            if config.colored_output {
-                writer.fg(term::color::BRIGHT_RED)?;
-            }
-            write!(writer, "\n{} {}", header_lvl, name)?;
+        let count = self
+            .commits
+            .iter()
+            .filter(|c| {
+                if c.is_compact(compact, config) {
+                    (c.summary.contains_tag(Some(tag))
+                        && !config.excluded_commit_tags.contains(&tag.to_owned()))
+                        || (tag == TOML_DEFAULT_KEY && c.summary.contains_untagged_elements())
+                } else {
+                    (c.contains_tag(Some(tag))
+                        && !config.excluded_commit_tags.contains(&tag.to_owned()))
+                        || (tag == TOML_DEFAULT_KEY && c.contains_untagged_elements())
+                }
+            })
+            .count();
+
+        if count > 0 {
+            // Long sections can be collapsed for GitLab, which needs the empty
+            // lines around the list to render the markdown within the HTML
+            let collapse = config.document_format == "gitlab" && count > config.collapse_threshold;
+            if collapse {
+                write!(
+                    writer,
+                    "\n<details>\n<summary>{}</summary>\n",
+                    escape_html(name)
+                )?;
+            } else {
+                if config.colored_output {
+                    writer.fg(config.colors.header)?;
+                }
+                write!(writer, "\n{}", section_header(name, *level, config))?;
 
-            writer.reset()?;
+                writer.reset()?;
+            }
 
             // Print commits for this tag
             for commit in &self.commits {
-                if compact {
+                if commit.is_compact(compact, config) {
                     commit.summary.print_default(writer, config, Some(tag))?;
                 } else {
                     commit.print_default(writer, config, Some(tag))?;
@@ -312,6 +537,9 @@ impl ParsedTag {
             }
 
             writeln!(writer)?;
+            if collapse {
+                writeln!(writer, "\n</details>")?;
+            }
 
             // Print footers if specified in the template
             if let Some(footers) = table.get(TOML_FOOTERS_KEY) {
@@ -359,7 +587,7 @@ impl ParsedTag {
                 let mut value = footer.value;
                 if config.show_commit_hash {
                     if let Some(oid) = footer.oid {
-                        value = format!("{} ({:.7})", value, oid);
+                        value = format!("{} ({})", value, format_oid(oid, config));
                     }
                 }
                 footer_tree.entry(footer.key).or_default().push(value);
@@ -371,26 +599,47 @@ impl ParsedTag {
             value.sort();
         }
 
-        // Print the mapped footers
-        for (key, values) in &footer_tree {
-            if config.colored_output {
-                writer.fg(term::color::BRIGHT_RED)?;
+        // Print the mapped footers, which are still grouped by the raw keys
+        match config.footer_style.as_str() {
+            "bold" => {
+                for (key, values) in &footer_tree {
+                    writeln!(
+                        writer,
+                        "\n**{}:** {}",
+                        footer_label(key, config),
+                        values.join(", ")
+                    )?;
+                }
             }
-            writeln!(writer, "\n{}:", key)?;
-            writer.reset()?;
-            let footer_string = values.join(", ");
-            let mut char_count = 0;
-            let mut footer_lines = String::new();
-            for cur_char in footer_string.chars() {
-                if char_count > 100 && cur_char == ' ' {
-                    footer_lines.push('\n');
-                    char_count = 0;
-                } else {
-                    footer_lines.push(cur_char);
-                    char_count += 1;
+            "table" if !footer_tree.is_empty() => {
+                writeln!(writer, "\n| Footer | Values |\n| --- | --- |")?;
+                for (key, values) in &footer_tree {
+                    let values = values.join(", ").replace('|', "\\|");
+                    writeln!(writer, "| {} | {} |", footer_label(key, config), values)?;
+                }
+            }
+            _ => {
+                for (key, values) in &footer_tree {
+                    if config.colored_output {
+                        writer.fg(config.colors.header)?;
+                    }
+                    writeln!(writer, "\n{}:", footer_label(key, config))?;
+                    writer.reset()?;
+                    let footer_string = values.join(", ");
+                    let mut char_count = 0;
+                    let mut footer_lines = String::new();
+                    for cur_char in footer_string.chars() {
+                        if char_count > 100 && cur_char == ' ' {
+                            footer_lines.push('\n');
+                            char_count = 0;
+                        } else {
+                            footer_lines.push(cur_char);
+                            char_count += 1;
+                        }
+                    }
+                    writeln!(writer, "{}", footer_lines)?;
                 }
             }
-            writeln!(writer, "{}", footer_lines)?;
         }
         Ok(())
     }
@@ -405,12 +654,154 @@ impl Tags for ParsedTag {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// A single commit message in its changelog ready form.
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct ParsedCommit {
+    /// The hash of the commit, if parsed from the git log
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: Option<Oid>,
+
+    /// The commit time in seconds since the epoch
+    pub time: Option<i64>,
+
+    /// True if the commit carries a signature, which is not verified
+    pub signed: bool,
+
+    /// The git note attached to the commit
+    pub note: Option<String>,
+
+    /// The paths changed compared to the first parent, if parsed from the git
+    /// log with `show_files_changed`
+    pub files_changed: Vec<String>,
+
+    /// The first line of the commit message
     pub summary: SummaryElement,
+
+    /// The lists and paragraphs of the commit message body
     pub body: Vec<BodyElement>,
+
+    /// The footers of the commit message, like `Reviewed-by: Me`
     pub footer: Vec<FooterElement>,
+
+    /// True if the commit should not be part of the changelog, which is the
+    /// case for a `Changelog: skip` footer
+    pub skip: bool,
+
+    /// True if the category of the summary is overridden by the trailer of
+    /// `category_override_trailer`
+    pub category_overridden: bool,
+}
+
+impl ParsedCommit {
+    /// Renders the commit like the short (`compact`) or detailed output
+    /// without any template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{Config, Parser};
+    ///
+    /// let mut config = Config::new();
+    /// config.colored_output = false;
+    /// let parser = Parser::new(config.clone());
+    /// let commit = parser
+    ///     .parse_commit_message("Added a feature\n\n- Fixed a bug", None)
+    ///     .unwrap();
+    /// assert_eq!(commit.render(&config, true), "- [Added] a feature");
+    /// assert_eq!(
+    ///     commit.render(&config, false),
+    ///     "- [Added] a feature\n    - [Fixed] a bug"
+    /// );
+    /// ```
+    pub fn render(&self, config: &Config, compact: bool) -> String {
+        // Writing into the buffer does not fail
+        let mut writer = Output::new_buffer();
+        let _ = if self.is_compact(compact, config) {
+            self.summary.print_default(&mut writer, config, None)
+        } else {
+            self.print_default(&mut writer, config, None)
+        };
+        writer
+            .as_str()
+            .map(|text| text.trim_start().to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns the first body element if it is a single line paragraph, which
+    /// will be printed as part of the summary line if
+    /// `treat_first_paragraph_as_subtitle` is configured.
+    fn subtitle(&self, config: &Config, tag: Option<&str>) -> Option<&ParagraphElement> {
+        if !config.treat_first_paragraph_as_subtitle {
+            return None;
+        }
+        match self.body.first() {
+            Some(BodyElement::Paragraph(paragraph))
+                if !paragraph.text.contains('\n')
+                    && paragraph.should_be_printed(tag)
+                    && !paragraph
+                        .tags
+                        .iter()
+                        .any(|x| config.excluded_commit_tags.contains(x)) =>
+            {
+                Some(paragraph)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks if the commit should be printed in its short form, which is not
+    /// the case for the `always_expand_categories`.
+    fn is_compact(&self, compact: bool, config: &Config) -> bool {
+        compact
+            && !config
+                .always_expand_categories
+                .contains(&self.summary.category)
+    }
+
+    /// Promotes every categorized list item of a squash merge commit to a
+    /// standalone commit. The commit is returned as it is if the body does not
+    /// only consist of list items with a category.
+    pub fn expand_squash(self) -> Vec<ParsedCommit> {
+        let is_squash = !self.body.is_empty()
+            && self.body.iter().all(|element| match element {
+                BodyElement::List(list) => list.iter().all(|item| !item.category.is_empty()),
+                BodyElement::Paragraph(_) => false,
+            });
+        if !is_squash {
+            return vec![self];
+        }
+
+        let mut footer = self.footer;
+        let mut commits = vec![];
+        for element in self.body {
+            if let BodyElement::List(list) = element {
+                for item in list {
+                    commits.push(ParsedCommit {
+                        oid: self.oid,
+                        time: self.time,
+                        signed: self.signed,
+                        note: self.note.clone(),
+                        files_changed: self.files_changed.clone(),
+                        summary: SummaryElement {
+                            oid: self.oid,
+                            prefix: self.summary.prefix.clone(),
+                            category: item.category,
+                            scope: None,
+                            signed: self.signed,
+                            text: item.text,
+                            tags: item.tags,
+                        },
+                        body: vec![],
+                        // Keep the footers only once to not accumulate them
+                        footer: mem::take(&mut footer),
+                        skip: self.skip,
+                        category_overridden: false,
+                    });
+                }
+            }
+        }
+        commits
+    }
 }
 
 impl Print for ParsedCommit {
@@ -429,12 +820,47 @@ impl Print for ParsedCommit {
         H: Fn(&mut T) -> Result<(), Error>,
     {
         // If summary is already filtered out then do not print at all
-        if self.summary.print(t, config, tag, c1, c2, c3)? == Printed::Nothing {
+        let subtitle = self.subtitle(config, tag);
+        let colors = PrintColors {
+            category: c1,
+            text: c2,
+            reset: c3,
+        };
+        if self.summary.print_with_subtitle(
+            subtitle.map(|paragraph| paragraph.text.as_str()),
+            t,
+            config,
+            tag,
+            &colors,
+        )? == Printed::Nothing
+        {
             return Ok(Printed::Nothing);
         }
-        for item in &self.body {
+        for item in self.body.iter().skip(usize::from(subtitle.is_some())) {
             item.print(t, config, tag, c1, c2, c3)?;
         }
+        if config.show_notes {
+            if let Some(ref note) = self.note {
+                let indent = indent(tag, config);
+                for line in note.lines() {
+                    write!(t, "\n{}{}", indent, line)?;
+                }
+            }
+        }
+        if config.show_files_changed {
+            let indent = indent(tag, config);
+            let bullet = list_bullet(config);
+            for file in self.files_changed.iter().take(config.max_files_changed) {
+                write!(t, "\n{}{} {}", indent, bullet, file)?;
+            }
+            let remaining = self
+                .files_changed
+                .len()
+                .saturating_sub(config.max_files_changed);
+            if remaining > 0 {
+                write!(t, "\n{}{} +{} more", indent, bullet, remaining)?;
+            }
+        }
         Ok(Printed::Something)
     }
 
@@ -464,69 +890,138 @@ impl Tags for ParsedCommit {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct SummaryElement {
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: Option<Oid>,
     pub prefix: String,
     pub category: String,
+    pub scope: Option<String>,
     pub text: String,
     pub tags: Vec<String>,
+    pub signed: bool,
 }
 
-impl Print for SummaryElement {
-    fn print<T: Write, F, G, H>(
+impl SummaryElement {
+    /// Returns true if the summary contains an excluded tag, which means that
+    /// the whole commit will not be printed.
+    fn is_excluded(&self, config: &Config) -> bool {
+        self.tags
+            .iter()
+            .any(|x| config.excluded_commit_tags.contains(x))
+    }
+
+    /// Prints the summary, where the `subtitle` is appended to the summary
+    /// text with the configured `subtitle_separator`.
+    fn print_with_subtitle<T: Write, F, G, H>(
         &self,
+        subtitle: Option<&str>,
         t: &mut T,
         config: &Config,
         tag: Option<&str>,
-        c1: &F,
-        c2: &G,
-        c3: &H,
+        colors: &PrintColors<F, G, H>,
     ) -> Result<Printed, Error>
     where
         F: Fn(&mut T) -> Result<(), Error>,
         G: Fn(&mut T) -> Result<(), Error>,
         H: Fn(&mut T) -> Result<(), Error>,
     {
+        let &PrintColors {
+            category: c1,
+            text: c2,
+            reset: c3,
+        } = colors;
+
         // Filter out excluded tags
-        if self
-            .tags
-            .iter()
-            .filter(|x| config.excluded_commit_tags.contains(x))
-            .count()
-            > 0_usize
-        {
+        if self.is_excluded(config) {
             return Ok(Printed::Nothing);
         }
 
         if self.should_be_printed(tag) {
-            write!(t, "\n- ")?;
+            let bullet = format!("{} ", list_bullet(config));
+            write!(t, "\n{}", bullet)?;
+            let mut column = bullet.chars().count();
             if config.show_prefix && !self.prefix.is_empty() {
                 write!(t, "{} ", self.prefix)?;
+                column += display_width(&self.prefix) + 1;
+            }
+            if config.show_category_emoji {
+                if let Some(emoji) = config.category_emoji.get(&self.category) {
+                    write!(t, "{} ", emoji)?;
+                    column += display_width(emoji) + 1;
+                }
             }
             if config.colored_output {
                 c1(t)?;
             }
-            write!(t, "{}", config.category_delimiters[0])?;
-            write!(t, "{}", self.category)?;
-            write!(t, "{} ", config.category_delimiters[1])?;
+            if !self.category.is_empty() {
+                let (open, close) = &config.category_delimiters[0];
+                let category = format!(
+                    "{}{}{} ",
+                    open,
+                    category_heading(&self.category, config),
+                    close
+                );
+                write!(t, "{}", category)?;
+                column += display_width(&category);
+            }
             if config.colored_output {
                 c2(t)?;
             }
-            write!(t, "{}", self.text)?;
+            let mut text = output_text(&self.text, config).into_owned();
+            if let Some(subtitle) = subtitle {
+                text += &config.subtitle_separator;
+                text += &output_text(subtitle, config);
+            }
 
             // Print the oid for the summary element (always)
             if config.show_commit_hash {
                 if let Some(oid) = self.oid {
-                    write!(t, " ({:.7})", oid)?;
+                    text += &format!(" ({})", format_oid(oid, config));
                 }
             }
+            if config.show_signature_status {
+                // The signature itself is not verified
+                text += if self.signed {
+                    " ✓ (unverified)"
+                } else {
+                    " ✗"
+                };
+            }
+            if let Some(width) = config.summary_wrap_width {
+                text = wrap_text(&text, column, width, &" ".repeat(bullet.chars().count()));
+            }
+            write!(t, "{}", text)?;
             if config.colored_output {
                 c3(t)?;
             }
         }
         Ok(Printed::Something)
     }
+}
+
+impl Print for SummaryElement {
+    fn print<T: Write, F, G, H>(
+        &self,
+        t: &mut T,
+        config: &Config,
+        tag: Option<&str>,
+        c1: &F,
+        c2: &G,
+        c3: &H,
+    ) -> Result<Printed, Error>
+    where
+        F: Fn(&mut T) -> Result<(), Error>,
+        G: Fn(&mut T) -> Result<(), Error>,
+        H: Fn(&mut T) -> Result<(), Error>,
+    {
+        let colors = PrintColors {
+            category: c1,
+            text: c2,
+            reset: c3,
+        };
+        self.print_with_subtitle(None, t, config, tag, &colors)
+    }
 
     fn contains_tag(&self, tag: Option<&str>) -> bool {
         match tag {
@@ -540,22 +1035,25 @@ impl Print for SummaryElement {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub enum BodyElement {
     List(Vec<ListElement>),
     Paragraph(ParagraphElement),
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct ListElement {
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: Option<Oid>,
+    pub level: usize,
     pub category: String,
     pub text: String,
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct ParagraphElement {
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: Option<Oid>,
     pub text: String,
     pub tags: Vec<String>,
@@ -578,8 +1076,18 @@ impl Print for BodyElement {
     {
         match *self {
             Self::List(ref vec) => {
+                let colors = PrintColors {
+                    category: c1,
+                    text: c2,
+                    reset: c3,
+                };
+                let mut previous = None;
                 for list_item in vec {
-                    list_item.print(t, config, tag, c1, c2, c3)?;
+                    if list_item.print_after(previous, t, config, tag, &colors)?
+                        == Printed::Something
+                    {
+                        previous = Some(list_item.category.as_str());
+                    }
                 }
             }
             Self::Paragraph(ref paragraph) => {
@@ -623,21 +1131,29 @@ impl Tags for BodyElement {
     }
 }
 
-impl Print for ListElement {
-    fn print<T: Write, F, G, H>(
+impl ListElement {
+    /// Prints the list item after the printed item with the `previous`
+    /// category. Consecutive items of the same category share a single
+    /// sub-header if `collapse_repeated_list_categories` is configured.
+    fn print_after<T: Write, F, G, H>(
         &self,
+        previous: Option<&str>,
         t: &mut T,
         config: &Config,
         tag: Option<&str>,
-        c1: &F,
-        c2: &G,
-        c3: &H,
+        colors: &PrintColors<F, G, H>,
     ) -> Result<Printed, Error>
     where
         F: Fn(&mut T) -> Result<(), Error>,
         G: Fn(&mut T) -> Result<(), Error>,
         H: Fn(&mut T) -> Result<(), Error>,
     {
+        let &PrintColors {
+            category: c1,
+            text: c2,
+            reset: c3,
+        } = colors;
+
         // Check if list item contains excluded tag
         if self
             .tags
@@ -649,57 +1165,121 @@ impl Print for ListElement {
             return Ok(Printed::Nothing);
         }
 
-        if self.should_be_printed(tag) {
-            write!(t, "\n{}- ", {
-                if tag.is_none() {
-                    iter::repeat(' ').take(4).collect::<String>()
-                } else {
-                    String::new()
-                }
-            })?;
-            if !self.category.is_empty() {
-                if config.colored_output {
-                    c1(t)?;
-                }
-                write!(t, "{}", config.category_delimiters[0])?;
-                write!(t, "{}", self.category)?;
-                write!(t, "{} ", config.category_delimiters[1])?;
-                if config.colored_output {
-                    c2(t)?;
-                }
+        if !self.should_be_printed(tag) {
+            return Ok(Printed::Nothing);
+        }
+
+        let mut depth = if tag.is_none() {
+            self.level + 1
+        } else {
+            self.level
+        };
+        let write_bullet = |t: &mut T, depth: usize| -> Result<(), Error> {
+            if tag.is_none() && config.document_format == "rst" {
+                // Nested lists need an empty line and the indent of the parent text
+                write!(t, "\n\n{}{}", "  ".repeat(depth), list_bullet(config))?;
+            } else {
+                let indent = indent(tag, config).repeat(depth);
+                write!(t, "\n{}{}", indent, list_bullet(config))?;
             }
-            write!(t, "{}", self.text)?;
-            // Print only in templating mode, otherwise hide unnecessary
-            // information
-            if config.show_commit_hash && tag.is_some() {
-                if let Some(oid) = self.oid {
-                    write!(t, " ({:.7})", oid)?;
+            Ok(())
+        };
+        let write_category = |t: &mut T| -> Result<(), Error> {
+            if config.colored_output {
+                c1(t)?;
+            }
+            let (open, close) = &config.category_delimiters[0];
+            write!(
+                t,
+                "{}{}{}",
+                open,
+                category_heading(&self.category, config),
+                close
+            )?;
+            if config.colored_output {
+                c2(t)?;
+            }
+            Ok(())
+        };
+
+        if config.collapse_repeated_list_categories && !self.category.is_empty() {
+            if previous != Some(self.category.as_str()) {
+                write_bullet(t, depth)?;
+                write!(t, " ")?;
+                write_category(t)?;
+                if config.colored_output {
+                    c3(t)?;
                 }
             }
+            depth += 1;
+            write_bullet(t, depth)?;
+            write!(t, " ")?;
             if config.colored_output {
-                c3(t)?;
+                c2(t)?;
+            }
+        } else {
+            write_bullet(t, depth)?;
+            write!(t, " ")?;
+            if !self.category.is_empty() {
+                write_category(t)?;
+                write!(t, " ")?;
             }
         }
-
-        Ok(Printed::Something)
-    }
-
-    fn contains_tag(&self, tag: Option<&str>) -> bool {
-        match tag {
-            Some(tag) => self.tags.contains(&tag.to_owned()),
-            None => true,
+        write!(t, "{}", output_text(&self.text, config))?;
+        // Print only in templating mode, otherwise hide unnecessary
+        // information
+        if config.show_commit_hash && tag.is_some() {
+            if let Some(oid) = self.oid {
+                write!(t, " ({})", format_oid(oid, config))?;
+            }
+        }
+        if config.colored_output {
+            c3(t)?;
         }
-    }
 
-    fn contains_untagged_elements(&self) -> bool {
-        self.tags.is_empty()
+        Ok(Printed::Something)
     }
 }
 
-impl Tags for ListElement {
-    fn get_tags(&self, mut vec: Vec<String>) -> Vec<String> {
-        vec.extend(self.tags.clone());
-        vec
+impl Print for ListElement {
+    fn print<T: Write, F, G, H>(
+        &self,
+        t: &mut T,
+        config: &Config,
+        tag: Option<&str>,
+        c1: &F,
+        c2: &G,
+        c3: &H,
+    ) -> Result<Printed, Error>
+    where
+        F: Fn(&mut T) -> Result<(), Error>,
+        G: Fn(&mut T) -> Result<(), Error>,
+        H: Fn(&mut T) -> Result<(), Error>,
+    {
+        let colors = PrintColors {
+            category: c1,
+            text: c2,
+            reset: c3,
+        };
+        self.print_after(None, t, config, tag, &colors)
+    }
+
+    fn contains_tag(&self, tag: Option<&str>) -> bool {
+        match tag {
+            Some(tag) => self.tags.contains(&tag.to_owned()),
+            None => true,
+        }
+    }
+
+    fn contains_untagged_elements(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+impl Tags for ListElement {
+    fn get_tags(&self, mut vec: Vec<String>) -> Vec<String> {
+        vec.extend(self.tags.clone());
+        vec
     }
 }
 
@@ -730,25 +1310,21 @@ impl Print for ParagraphElement {
         }
 
         if self.should_be_printed(tag) {
-            for (index, line) in self.text
-                    .lines()
-                    .map(|x| {
-                             let indent = if tag.is_none() { 4 } else { 2 };
-                             iter::repeat(' ').take(indent).collect::<String>()
-                         } + x)
-                    .collect::<Vec<String>>()
-                    .iter()
-                    .enumerate() {
+            let indent = indent(tag, config);
+            for (index, line) in output_text(&self.text, config).lines().enumerate() {
                 if tag.is_some() && index == 0 {
                     // Paragraphs will be transformed into lists when using templates
-                    write!(t, "\n{}", line.replace("  ", "- "))?;
+                    write!(t, "\n{} {}", list_bullet(config), line)?;
+                } else if tag.is_none() && config.document_format == "rst" {
+                    // Nested paragraphs need an empty line and the indent of the parent text
+                    write!(t, "\n{}  {}", if index == 0 { "\n" } else { "" }, line)?;
                 } else {
-                    write!(t, "\n{}", line)?;
+                    write!(t, "\n{}{}", indent, line)?;
                 }
                 // Print only in templating mode, otherwise hide unnecessary information
                 if config.show_commit_hash && tag.is_some() {
                     if let Some(oid) = self.oid {
-                        write!(t, " ({:.7})", oid)?;
+                        write!(t, " ({})", format_oid(oid, config))?;
                     }
                 }
             }
@@ -768,8 +1344,9 @@ impl Print for ParagraphElement {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
 pub struct FooterElement {
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: Option<Oid>,
     pub key: String,
     pub value: String,
@@ -785,33 +1362,206 @@ lazy_static! {
         .multi_line(true)
         .build()
         .unwrap();
+    static ref RE_SQUASH_LIST: Regex = RegexBuilder::new(r"^\*\s.*$(\n^\s+[-*]\s.*)*")
+        .multi_line(true)
+        .build()
+        .unwrap();
     static ref RE_PARAGRAPH: Regex = RegexBuilder::new(r"^\w").multi_line(true).build().unwrap();
-    static ref RE_COMMENT: Regex = RegexBuilder::new(r"^#.*").multi_line(true).build().unwrap();
+    static ref RE_INLINE_MARKDOWN: Vec<Regex> = vec![
+        Regex::new(r"\*\*([^*]+)\*\*").unwrap(),
+        Regex::new(r"\b__([^_]+)__\b").unwrap(),
+        Regex::new(r"`([^`]+)`").unwrap(),
+        Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*").unwrap(),
+        Regex::new(r"\b_([^_\s](?:[^_]*[^_\s])?)_\b").unwrap(),
+    ];
+    static ref RE_MARKDOWN_LINK: Regex = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap();
 }
 
 type ParserResult<'a, T> = IResult<&'a [u8], T>;
 
+/// A commit message parsing error, which contains the byte offset within the
+/// commit message where the parsing failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The summary line does not match the commit message format
+    Summary {
+        /// The trimmed summary line
+        line: String,
+        /// The byte offset where the summary parsing failed
+        offset: usize,
+    },
+
+    /// A part of the commit message body could not be parsed
+    Part {
+        /// The unparsable commit message part
+        part: String,
+        /// The byte offset where the part starts
+        offset: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Summary { line, .. } => write!(f, "Summary parsing failed: '{}'", line),
+            ParseError::Part { part, .. } => {
+                write!(f, "Could not parse commit message part: '{}'", part)
+            }
+        }
+    }
+}
+
+impl Fail for ParseError {}
+
+impl ParseError {
+    /// The byte offset within the commit message where the parsing failed.
+    pub fn offset(&self) -> usize {
+        match self {
+            ParseError::Summary { offset, .. } | ParseError::Part { offset, .. } => *offset,
+        }
+    }
+}
+
+/// The commit message parser, which holds the parsed tags of the log as well.
 #[derive(Clone)]
 pub struct Parser {
+    /// The configuration used for the parsing and the output
     pub config: Config,
+
+    /// The parsed tags including their commits
     pub result: Vec<ParsedTag>,
+
+    /// The compiled regex to find a category together with the categories it
+    /// has been compiled of
+    category_finder: (Vec<String>, bytes::Regex),
 }
 
 impl Parser {
-    fn parse_category<'a>(&self, input: &'a [u8]) -> ParserResult<'a, &'a str> {
-        let cat_finder = bytes::Regex::new(&self.config.categories.join("|")).unwrap();
+    /// Constructs a new `Parser` without any parsed tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{Config, Parser};
+    ///
+    /// let parser = Parser::new(Config::new());
+    /// assert!(parser.parse_commit_message("Added a parser", None).is_ok());
+    /// ```
+    pub fn new(config: Config) -> Self {
+        let category_finder = (
+            config.categories.clone(),
+            build_category_finder(&config.categories),
+        );
+        Self {
+            config,
+            result: vec![],
+            category_finder,
+        }
+    }
+
+    /// Replaces the configuration, where the category regex is compiled again
+    /// only if the categories have been changed.
+    pub fn set_config(&mut self, config: Config) {
+        if config.categories != self.category_finder.0 {
+            self.category_finder = (
+                config.categories.clone(),
+                build_category_finder(&config.categories),
+            );
+        }
+        self.config = config;
+    }
+
+    /// Returns the regex to find a category at the start of the input, which
+    /// is compiled again if the categories have been changed without
+    /// `set_config`.
+    fn category_finder(&self) -> bytes::Regex {
+        let (ref categories, ref finder) = self.category_finder;
+        if *categories == self.config.categories {
+            finder.clone()
+        } else {
+            build_category_finder(&self.config.categories)
+        }
+    }
+
+    /// Parses the category and an optional scope, which can be attached within
+    /// the delimiters like `[Fixed:parser]`.
+    fn parse_category<'a>(&self, input: &'a [u8]) -> ParserResult<'a, (&'a str, Option<&'a str>)> {
+        let cat_finder = self.category_finder();
+
+        // The category may be wrapped in any of the delimiter pairs, where the
+        // closing delimiter has to match the opening one
+        let mut input = input;
+        let mut closing = self
+            .config
+            .category_delimiters
+            .iter()
+            .map(|(_, close)| close)
+            .collect::<Vec<_>>();
+        let mut opened = false;
+        for (open, close) in &self.config.category_delimiters {
+            if let (rest, Some(_)) = opt(tag(open.as_str()))(input)? {
+                input = rest;
+                closing = vec![close];
+                opened = true;
+                break;
+            }
+        }
+        let (mut input, p_category) = map_res(re_find(cat_finder), str::from_utf8)(input)?;
+        let separator = self.config.category_scope_separator.as_str();
+        let mut p_scope = None;
+        if opened && !separator.is_empty() {
+            let is_end = |c: u8| {
+                c.is_ascii_whitespace()
+                    || closing
+                        .iter()
+                        .any(|close| close.as_bytes().first() == Some(&c))
+            };
+            let (rest, scope) = opt(preceded(
+                tag(separator),
+                map_res(take_till1(is_end), str::from_utf8),
+            ))(input)?;
+            input = rest;
+            p_scope = scope;
+        }
+        for close in closing {
+            if let (rest, Some(_)) = opt(tag(close.as_str()))(input)? {
+                input = rest;
+                break;
+            }
+        }
+        Ok((input, (p_category, p_scope)))
+    }
+
+    /// Parses a conventional commit type like `feat(parser)!:`, where the
+    /// categories are used as types.
+    fn parse_conventional_type<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> ParserResult<'a, (&'a str, Option<&'a str>)> {
+        let cat_finder = self.category_finder();
 
-        let (input, _) = opt(tag(self.config.category_delimiters[0].as_str()))(input)?;
         let (input, p_category) = map_res(re_find(cat_finder), str::from_utf8)(input)?;
-        let (input, _) = opt(tag(self.config.category_delimiters[1].as_str()))(input)?;
-        Ok((input, p_category))
+        let (input, p_scope) = opt(delimited(
+            char('('),
+            map_res(take_till1(|c| c == b')'), str::from_utf8),
+            char(')'),
+        ))(input)?;
+        let (input, _) = opt(char('!'))(input)?;
+        let (input, _) = char(':')(input)?;
+        Ok((input, (p_category, p_scope)))
     }
 
     fn parse_list_item<'a>(&mut self, input: &'a [u8]) -> ParserResult<'a, ListElement> {
         let (input, _) = space0(input)?;
-        let (input, _) = tag("-")(input)?;
+        let bullets = if self.config.expand_squash_commits {
+            "-*"
+        } else {
+            "-"
+        };
+        let (input, _) = one_of(bullets)(input)?;
         let (input, _) = space0(input)?;
         let (input, p_category) = opt(|input| self.parse_category(input))(input)?;
+        let p_category = p_category.map(|(category, _)| category);
         let (input, _) = space0(input)?;
         let (input, p_tags_rest) = map(rest, Self::parse_and_consume_tags)(input)?;
 
@@ -819,6 +1569,7 @@ impl Parser {
             input,
             ListElement {
                 oid: None,
+                level: 0,
                 category: p_category.unwrap_or("").to_owned(),
                 tags: p_tags_rest.0,
                 text: p_tags_rest.1,
@@ -829,8 +1580,20 @@ impl Parser {
     fn parse_summary<'a>(&mut self, input: &'a [u8]) -> ParserResult<'a, SummaryElement> {
         let (input, p_prefix) = opt(separated_pair(alpha1, char('-'), digit1))(input)?;
         let (input, _) = space0(input)?;
-        let (input, p_category) = self.parse_category(input)?;
-        let (input, _) = space1(input)?;
+        let (input, (p_category, p_scope)) = if self.config.commit_style == "conventional" {
+            self.parse_conventional_type(input)?
+        } else if self.config.require_category {
+            self.parse_category(input)?
+        } else {
+            map(opt(|input| self.parse_category(input)), |c| {
+                c.unwrap_or(("", None))
+            })(input)?
+        };
+        let (input, _) = if p_category.is_empty() {
+            space0(input)?
+        } else {
+            space1(input)?
+        };
         let (input, p_tags_rest) = map(rest, Self::parse_and_consume_tags)(input)?;
 
         Ok((
@@ -841,6 +1604,8 @@ impl Parser {
                     format!("{}-{}", str_or_empty(p.0), str_or_empty(p.1))
                 }),
                 category: p_category.to_owned(),
+                scope: p_scope.map(str::to_owned),
+                signed: false,
                 tags: p_tags_rest.0,
                 text: p_tags_rest.1,
             },
@@ -880,6 +1645,9 @@ impl Parser {
         message: &str,
         oid: Option<Oid>,
     ) -> Result<ParsedCommit, Error> {
+        // Some editors write a leading BOM into the message
+        let message = message.trim_start_matches('\u{feff}');
+
         // Every block is split by two newlines
         let mut commit_parts = message.split("\n\n");
 
@@ -888,37 +1656,97 @@ impl Parser {
             .next()
             .ok_or_else(|| format_err!("Summar line parsing: Commit message length too small."))?
             .trim();
+        let mut lenient_paragraph = None;
         let mut parsed_summary = match self.clone().parse_summary(summary_line.as_bytes()) {
             Ok((_, parsed)) => parsed,
-            _ => bail!("Summary parsing failed: '{}'", summary_line),
+            Err(_) if self.config.lenient_summary => {
+                // The first line becomes the summary, the rest of the block a paragraph
+                let mut lines = summary_line.splitn(2, '\n');
+                let first_line = lines.next().unwrap_or_default().trim();
+                lenient_paragraph = lines.next().map(str::trim).filter(|p| !p.is_empty());
+                let (tags, text) = Self::parse_and_consume_tags(first_line.as_bytes());
+                SummaryElement {
+                    oid: None,
+                    prefix: "".to_owned(),
+                    category: "".to_owned(),
+                    scope: None,
+                    text,
+                    tags,
+                    signed: false,
+                }
+            }
+            Err(e) => {
+                // The remaining input of the parser marks the failure position
+                let remaining = match e {
+                    nom::Err::Error(e) | nom::Err::Failure(e) => e.input.len(),
+                    nom::Err::Incomplete(_) => 0,
+                };
+                return Err(ParseError::Summary {
+                    line: summary_line.to_owned(),
+                    offset: offset_in(message, summary_line) + summary_line.len() - remaining,
+                }
+                .into());
+            }
         };
         parsed_summary.oid = oid;
 
         // Parse the body and the footer, the summary is already consumed
         let mut parsed_footer = vec![];
         let mut parsed_body = vec![];
+        if let Some(paragraph) = lenient_paragraph {
+            let (tags, text) = Self::parse_and_consume_tags(paragraph.as_bytes());
+            parsed_body.push(BodyElement::Paragraph(ParagraphElement {
+                oid,
+                text: text.trim().to_owned(),
+                tags,
+            }));
+        }
+
+        // A configured footer pattern replaces the default `Key: value` one
+        let re_footer = self
+            .config
+            .footer_pattern
+            .as_ref()
+            .map_or(&*RE_FOOTER, FooterPattern::regex);
 
         // Iterate over all the commit message parts
         for part in commit_parts {
             // Do nothing on comments and empty parts
-            if RE_COMMENT.is_match(part) || part.is_empty() {
+            if part.lines().any(|line| self.config.is_comment_line(line)) || part.is_empty() {
                 continue;
             }
 
             // Parse the footer
-            if RE_FOOTER.is_match(part) {
-                for cap in RE_FOOTER.captures_iter(part) {
-                    let key = cap.get(1).map_or(part, |k| k.as_str()).to_owned();
-                    let value = cap.get(2).map_or(part, |k| k.as_str()).to_owned();
-                    parsed_footer.push(FooterElement { oid, key, value });
+            if re_footer.is_match(part) {
+                for cap in re_footer.captures_iter(part) {
+                    // Optional groups of a footer pattern may not participate
+                    if let (Some(key), Some(value)) = (cap.get(1), cap.get(2)) {
+                        parsed_footer.push(FooterElement {
+                            oid,
+                            key: key.as_str().to_owned(),
+                            value: value.as_str().to_owned(),
+                        });
+                    }
                 }
 
-            // Parse all list items
-            } else if RE_LIST.is_match(part) {
+            // Parse all list items, squash merge commits use asterisks
+            } else if RE_LIST.is_match(part)
+                || (self.config.expand_squash_commits && RE_SQUASH_LIST.is_match(part))
+            {
                 let mut list = vec![];
+                let mut indents: Vec<usize> = vec![];
                 for list_item in part.lines() {
                     if let Ok((_, mut result)) = self.clone().parse_list_item(list_item.as_bytes())
                     {
+                        // The nesting level results from the deeper indentations before
+                        let indent = indent_width(list_item);
+                        while indents.last().map(|&i| i > indent).unwrap_or(false) {
+                            indents.pop();
+                        }
+                        if indents.last().map(|&i| i < indent).unwrap_or(true) {
+                            indents.push(indent);
+                        }
+                        result.level = indents.len() - 1;
                         result.oid = oid;
                         list.push(result);
                     };
@@ -936,34 +1764,165 @@ impl Parser {
 
             // Nothing matched, this should not happen at all
             } else {
-                bail!("Could not parse commit message part: '{}'", part);
+                return Err(ParseError::Part {
+                    part: part.to_owned(),
+                    offset: offset_in(message, part),
+                }
+                .into());
+            }
+        }
+
+        // Commits can opt out of the changelog via a trailer
+        let skip = parsed_footer.iter().any(|footer| {
+            footer.key.eq_ignore_ascii_case("Changelog")
+                && footer.value.trim().eq_ignore_ascii_case("skip")
+        });
+
+        // A trailer can override the category of the summary
+        let mut category_overridden = false;
+        if let Some(ref trailer) = self.config.category_override_trailer {
+            if let Some(footer) = parsed_footer
+                .iter()
+                .find(|footer| footer.key.eq_ignore_ascii_case(trailer))
+            {
+                parsed_summary.category = footer.value.trim().to_owned();
+                category_overridden = true;
             }
         }
 
         Ok(ParsedCommit {
             oid,
+            time: None,
+            signed: false,
+            note: None,
+            files_changed: vec![],
             summary: parsed_summary,
             body: parsed_body,
             footer: parsed_footer,
+            skip,
+            category_overridden,
         })
     }
 
-    /// Prints the commits without any template
+    /// Returns true if the whole `category` is matched by the configured
+    /// categories, which may be regular expressions as well.
+    #[allow(clippy::unnecessary_map_or)]
+    pub(crate) fn is_category(&self, category: &str) -> bool {
+        self.category_finder()
+            .find(category.as_bytes())
+            .map_or(false, |m| m.end() == category.len())
+    }
+
+    /// Prints the commits without any template. The output format
+    /// (`"terminal"`, `"markdown"` or `"plain"`) decides about the colors and
+    /// inline markdown independently from the used writer. The `"json-lines"`
+    /// format prints every commit as a single line of JSON instead, whereas
+    /// the `"digest"` format prints a single line per tag with the number of
+    /// commits per category.
     pub fn print(
         &self,
         compact: bool,
         template: Option<&str>,
+        format: &str,
         writer: &mut Output,
     ) -> Result<(), Error> {
-        // Print every tag
-        for (index, tag) in self.result.iter().enumerate() {
-            tag.print_to_term_and_write_to_vector(
-                writer,
-                compact,
-                &self.config,
-                template,
-                (index, self.result.len()),
-            )?;
+        self.print_to(compact, template, format, false, writer)
+    }
+
+    /// Prints the commits like `print`, where the commit text of markdown
+    /// output files is escaped unless `escape_markdown` is disabled.
+    pub(crate) fn print_to(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        format: &str,
+        to_file: bool,
+        writer: &mut Output,
+    ) -> Result<(), Error> {
+        let mut config = self.config.clone();
+        if to_file {
+            config.escape_markdown.get_or_insert(true);
+        }
+        match format {
+            "json-lines" => return self.print_json_lines(writer),
+            "digest" => {
+                for tag in &self.result {
+                    tag.print_digest(writer, &config)?;
+                }
+                return Ok(());
+            }
+            "terminal" => config.escape_markdown = Some(false),
+            "plain" => {
+                config.colored_output = false;
+                config.escape_terminal_markdown = true;
+                config.escape_markdown = Some(false);
+            }
+            _ => {
+                // Inline markdown is kept as it is
+                config.colored_output = false;
+                config.escape_terminal_markdown = false;
+                config.render_markdown_links = "off".to_owned();
+            }
+        }
+
+        // Plain text documents like reStructuredText always need a title
+        if config.document_format == "rst" {
+            config.colored_output = false;
+            config.escape_markdown = Some(false);
+            let title = config.title.as_deref().unwrap_or("Changelog");
+            write!(writer, "{}", section_header(title, 0, &config))?;
+        } else if let Some(ref title) = config.title {
+            writeln!(writer, "{}", section_header(title, 1, &config))?;
+        }
+
+        if config.generate_toc && config.document_format == "markdown" {
+            self.print_toc(writer, &config)?;
+        }
+
+        // Parse the template only once for all tags
+        let template = match template {
+            Some(path) => {
+                let mut file = File::open(path)?;
+                let mut toml_string = String::new();
+                file.read_to_string(&mut toml_string)?;
+                Some(toml::from_str::<Value>(&toml_string)?)
+            }
+            None => None,
+        };
+
+        // Every tag renders independently from the others, which is done in
+        // parallel if no colors have to be written into the terminal
+        let len = self.result.len();
+        if template.is_some() && config.render_parallel && !writer.is_colored() {
+            let rendered = self
+                .result
+                .par_iter()
+                .enumerate()
+                .map(|(index, tag)| {
+                    let mut buffer = Output::new_buffer();
+                    tag.print_to_term_and_write_to_vector(
+                        &mut buffer,
+                        compact,
+                        &config,
+                        template.as_ref(),
+                        (index, len),
+                    )?;
+                    Ok(buffer.into_bytes().unwrap_or_default())
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            for bytes in rendered {
+                writer.write_all(&bytes)?;
+            }
+        } else {
+            for (index, tag) in self.result.iter().enumerate() {
+                tag.print_to_term_and_write_to_vector(
+                    writer,
+                    compact,
+                    &config,
+                    template.as_ref(),
+                    (index, len),
+                )?;
+            }
         }
 
         if !writer.is_buffered() {
@@ -972,6 +1931,47 @@ impl Parser {
         Ok(())
     }
 
+    /// Prints every commit together with its tag name as a JSON object per
+    /// line, which can be processed line by line.
+    fn print_json_lines(&self, writer: &mut Output) -> Result<(), Error> {
+        #[derive(Serialize)]
+        struct JsonLine<'a> {
+            tag: &'a str,
+            commit: &'a ParsedCommit,
+        }
+
+        for tag in &self.result {
+            for commit in &tag.commits {
+                if !commit.summary.is_excluded(&self.config) {
+                    let line = JsonLine {
+                        tag: &tag.name,
+                        commit,
+                    };
+                    writeln!(writer, "{}", serde_json::to_string(&line)?)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints a list of links to every version header
+    fn print_toc(&self, writer: &mut Output, config: &Config) -> Result<(), Error> {
+        let mut slugs: BTreeMap<String, usize> = BTreeMap::new();
+        for tag in &self.result {
+            let mut slug = github_slug(&format!("{} {}", tag.name, tag.header_suffix(config)));
+
+            // Duplicate headers get a numbered suffix
+            let count = slugs.entry(slug.clone()).or_insert(0);
+            if *count > 0 {
+                slug = format!("{}-{}", slug, count);
+            }
+            *count += 1;
+
+            writeln!(writer, "- [{}](#{})", tag.name, slug)?;
+        }
+        Ok(())
+    }
+
     /// Returns all tags recursively from a toml table
     pub fn get_tags_from_toml(
         &self,
@@ -997,21 +1997,257 @@ impl Parser {
     }
 }
 
+/// Get the commit text for the output, which renders markdown links, strips
+/// simple inline markdown or escapes the markdown if configured
+fn output_text<'a>(text: &'a str, config: &Config) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(text);
+    let replacement = match config.render_markdown_links.as_str() {
+        "url" => Some("$1 ($2)"),
+        "text" => Some("$1"),
+        _ => None,
+    };
+    if let Some(replacement) = replacement {
+        if let Cow::Owned(rendered) = RE_MARKDOWN_LINK.replace_all(&text, replacement) {
+            text = Cow::Owned(rendered);
+        }
+    }
+    if config.escape_terminal_markdown {
+        for regex in RE_INLINE_MARKDOWN.iter() {
+            if let Cow::Owned(stripped) = regex.replace_all(&text, "$1") {
+                text = Cow::Owned(stripped);
+            }
+        }
+    }
+    if config.escape_markdown == Some(true) {
+        if let Cow::Owned(escaped) = escape_markdown(&text) {
+            text = Cow::Owned(escaped);
+        }
+    }
+    text
+}
+
+/// Escapes the characters which would change the markdown structure, like a
+/// leading `#` turning the text into a heading, a leading `-`, `+`, `>` or
+/// `1.` starting a list or quote, or a single `*` starting an emphasis.
+/// Inline markdown like `code`, **bold** or links is kept.
+#[allow(clippy::unnecessary_map_or)]
+fn escape_markdown(text: &str) -> Cow<'_, str> {
+    let kept = RE_INLINE_MARKDOWN
+        .iter()
+        .chain(iter::once(&*RE_MARKDOWN_LINK))
+        .flat_map(|regex| regex.find_iter(text))
+        .map(|found| found.range())
+        .collect::<Vec<_>>();
+
+    let mut escaped = String::with_capacity(text.len());
+    let mut line_start = true;
+    let mut line_number = false;
+    let mut previous = None;
+    for (index, c) in text.char_indices() {
+        let next = text[index + c.len_utf8()..].chars().next();
+        let needs_escape = previous != Some('\\')
+            && !kept.iter().any(|range| range.contains(&index))
+            && match c {
+                '#' | '>' => line_start,
+                '-' | '+' => line_start && next.map_or(true, char::is_whitespace),
+                // Only numbers at the start of a line form an ordered list
+                '.' | ')' => line_number && next.map_or(true, char::is_whitespace),
+                '*' | '[' => true,
+                // Underscores within words never start an emphasis
+                '_' => {
+                    !(previous.map_or(false, char::is_alphanumeric)
+                        && next.map_or(false, char::is_alphanumeric))
+                }
+                _ => false,
+            };
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        line_number = c.is_ascii_digit() && (line_start || line_number);
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+        previous = Some(c);
+    }
+
+    if escaped.len() == text.len() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(escaped)
+    }
+}
+
+/// Wraps the words of the text at the given width, where the first line starts
+/// at the given column and all continuation lines with the indent
+fn wrap_text(text: &str, column: usize, width: usize, indent: &str) -> String {
+    let mut wrapped = String::new();
+    let mut column = column;
+    for (index, word) in text.split(' ').enumerate() {
+        let length = display_width(word);
+        if index > 0 {
+            if column + 1 + length > width {
+                wrapped.push('\n');
+                wrapped.push_str(indent);
+                column = display_width(indent);
+            } else {
+                wrapped.push(' ');
+                column += 1;
+            }
+        }
+        wrapped.push_str(word);
+        column += length;
+    }
+    wrapped
+}
+
+/// The number of terminal columns of the text, where emoji take two columns
+/// and joiners or variation selectors none at all
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x200D | 0xFE00..=0xFE0F => 0,
+            0x2600..=0x27BF | 0x1F000..=0x1FAFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Serializes the commit hash as hex string
+fn serialize_oid<S: Serializer>(oid: &Option<Oid>, serializer: S) -> Result<S::Ok, S::Error> {
+    match oid {
+        Some(oid) => serializer.serialize_some(&oid.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_date<S: Serializer>(date: &Date<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date.format("%Y-%m-%d").to_string())
+}
+
+/// Escape the characters which are not allowed within HTML text
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The indentation of nested elements, which is halved in templating mode
+fn indent(tag: Option<&str>, config: &Config) -> String {
+    let width = if tag.is_none() {
+        config.indent_width
+    } else {
+        config.indent_width / 2
+    };
+    " ".repeat(width)
+}
+
+/// The width of the leading whitespace of a line, where tabs count as four
+/// spaces
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// The displayed label of a footer key, which is the configured label or the
+/// key itself
+fn footer_label<'a>(key: &'a str, config: &'a Config) -> &'a str {
+    config.footer_labels.get(key).map_or(key, String::as_str)
+}
+
+/// The displayed name of a category, which is the configured heading or the
+/// category itself
+fn category_heading<'a>(category: &'a str, config: &'a Config) -> &'a str {
+    config
+        .category_headings
+        .get(category)
+        .map_or(category, String::as_str)
+}
+
+/// Builds the regex to find a category at the start of the input, where
+/// categories ending with a word character have to end at a word boundary to
+/// not match the beginning of a longer word like `Address`.
+fn build_category_finder(categories: &[String]) -> bytes::Regex {
+    let alternatives = categories
+        .iter()
+        .map(|category| {
+            if category.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                format!(r"{}\b", category)
+            } else {
+                category.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    bytes::Regex::new(&format!("^(?:{})", alternatives.join("|"))).unwrap()
+}
+
+/// The list bullet of the output format
+fn list_bullet(config: &Config) -> &str {
+    if config.document_format == "rst" {
+        "*"
+    } else {
+        &config.bullet_char
+    }
+}
+
+/// Formats a commit hash as inline literal for reStructuredText
+fn format_oid(oid: Oid, config: &Config) -> String {
+    if config.document_format == "rst" {
+        format!("``{:.7}``", oid)
+    } else {
+        format!("{:.7}", oid)
+    }
+}
+
+/// Returns the header at the given level, where reStructuredText needs an
+/// underline which is at least as long as the header text and an empty line
+/// afterwards
+fn section_header(name: &str, level: usize, config: &Config) -> String {
+    if config.document_format == "rst" {
+        let underline = match level {
+            0 => '=',
+            1 => '-',
+            2 => '~',
+            3 => '^',
+            _ => '"',
+        };
+        let underline: String = iter::repeat(underline).take(name.chars().count()).collect();
+        format!("{}\n{}\n", name, underline)
+    } else {
+        let header_lvl: String = iter::repeat('#').take(level).collect();
+        format!("{} {}", header_lvl, name)
+    }
+}
+
+/// Converts a header into its anchor like GitHub does
+fn github_slug(header: &str) -> String {
+    header
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
 /// Get valid string from bytes or an empty string
 fn str_or_empty(input: &[u8]) -> &str {
     str::from_utf8(input).unwrap_or("")
 }
 
+/// Returns the byte offset of a sub slice within its parent string.
+fn offset_in(parent: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - parent.as_ptr() as usize
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::{self, Config};
+    use chrono::TimeZone;
+    use std::fs;
 
     fn get_parser() -> Parser {
-        Parser {
-            config: Config::new(),
-            result: vec![],
-        }
+        Parser::new(Config::new())
     }
 
     fn parse_and_print_error(message: &str) {
@@ -1022,6 +2258,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_category_changed_categories() {
+        let mut parser = get_parser();
+        assert!(parser.parse_commit_message("Added something", None).is_ok());
+        assert!(parser
+            .parse_commit_message("Tested something", None)
+            .is_err());
+
+        assert_eq!(parser.category_finder.0, parser.config.categories);
+
+        // Directly changed categories are used without the cached finder
+        parser.config.categories = vec!["Tested".to_owned()];
+        assert!(parser
+            .parse_commit_message("Added something", None)
+            .is_err());
+        assert!(parser
+            .parse_commit_message("Tested something", None)
+            .is_ok());
+        assert_ne!(parser.category_finder.0, parser.config.categories);
+
+        // Setting the configuration compiles the finder again
+        let mut config = Config::new();
+        config.categories = vec!["Reviewed".to_owned()];
+        parser.set_config(config);
+        assert_eq!(parser.category_finder.0, vec!["Reviewed".to_owned()]);
+        assert!(parser
+            .parse_commit_message("Reviewed something", None)
+            .is_ok());
+        assert!(parser
+            .parse_commit_message("Tested something", None)
+            .is_err());
+    }
+
     #[test]
     fn parse_commit_ok_1() {
         let commit = get_parser().parse_commit_message(
@@ -1136,6 +2405,1216 @@ mod tests {
         }
     }
 
+    #[test]
+    fn output_text_inline_markdown() {
+        let text = "use `foo_bar` with **bold**, __strong__, *italic* and _em_ in snake_case_names";
+        let mut config = Config::new();
+        assert_eq!(output_text(text, &config), text);
+        config.escape_terminal_markdown = true;
+        assert_eq!(
+            output_text(text, &config),
+            "use foo_bar with bold, strong, italic and em in snake_case_names"
+        );
+        assert_eq!(output_text("2 * 3 * 4", &config), "2 * 3 * 4");
+    }
+
+    #[test]
+    fn output_text_escape_markdown() {
+        let mut config = Config::new();
+        assert_eq!(output_text("# a heading", &config), "# a heading");
+        config.escape_markdown = Some(true);
+        assert_eq!(
+            output_text("# not a heading\n  # neither, but #1", &config),
+            "\\# not a heading\n  \\# neither, but #1"
+        );
+        assert_eq!(
+            output_text("> no quote\n- no list\n+ neither\n12. nor this", &config),
+            "\\> no quote\n\\- no list\n\\+ neither\n12\\. nor this"
+        );
+        assert_eq!(
+            output_text("a-b, 1 - 2 and version 1.2 of v1.", &config),
+            "a-b, 1 - 2 and version 1.2 of v1."
+        );
+        assert_eq!(
+            output_text("2 * 3 for [x] and _private in snake_case", &config),
+            "2 \\* 3 for \\[x] and \\_private in snake_case"
+        );
+        assert_eq!(
+            output_text("already \\* escaped", &config),
+            "already \\* escaped"
+        );
+    }
+
+    #[test]
+    fn print_escape_markdown() {
+        let mut parser = get_parser();
+        parser.config.comment_char = ";".to_owned();
+        parser.config.lenient_summary = true;
+        let commit = parser
+            .parse_commit_message("# Release highlights", None)
+            .unwrap();
+        parser.result = vec![ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![commit],
+            message_ids: vec![0],
+        }];
+
+        // Only the output files are escaped by default
+        let mut writer = Output::new_buffer();
+        parser
+            .print_to(true, None, "markdown", true, &mut writer)
+            .unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("\n- \\# Release highlights"));
+
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("\n- # Release highlights"));
+
+        let mut writer = Output::new_buffer();
+        parser
+            .print_to(true, None, "plain", true, &mut writer)
+            .unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("\n- # Release highlights"));
+
+        parser.config.escape_markdown = Some(false);
+        let mut writer = Output::new_buffer();
+        parser
+            .print_to(true, None, "markdown", true, &mut writer)
+            .unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("\n- # Release highlights"));
+    }
+
+    #[test]
+    fn output_text_markdown_links() {
+        let text = "see [the docs](https://example.com/a_b) and [broken] (link)";
+        let mut config = Config::new();
+        assert_eq!(output_text(text, &config), text);
+        config.render_markdown_links = "url".to_owned();
+        assert_eq!(
+            output_text(text, &config),
+            "see the docs (https://example.com/a_b) and [broken] (link)"
+        );
+        config.render_markdown_links = "text".to_owned();
+        config.escape_terminal_markdown = true;
+        assert_eq!(
+            output_text(text, &config),
+            "see the docs and [broken] (link)"
+        );
+
+        let mut parser = Parser::new(config);
+        let commit = parser
+            .parse_commit_message("Added [link](https://example.com)", None)
+            .unwrap();
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![commit],
+            message_ids: vec![0],
+        });
+        let mut writer = Output::new_buffer();
+        parser.print(false, None, "markdown", &mut writer).unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("[link](https://example.com)"));
+    }
+
+    fn print_template_sections(config: &Config) -> String {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![
+                parser
+                    .parse_commit_message("Added one <thing> :tag1:", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Fixed two things :tag1:", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Removed something", None)
+                    .unwrap(),
+            ],
+            message_ids: vec![],
+        };
+        let template: Value = toml::from_str(
+            "[[tag]]\ntag = \"default\"\nname = \"Default\"\n\n\
+             [[tag]]\ntag = \"tag1\"\nname = \"Section <1>\"",
+        )
+        .unwrap();
+        let mut writer = Output::new_buffer();
+        assert!(tag
+            .print_commits_in_table(
+                &mut writer,
+                template.as_table().unwrap(),
+                &mut 1,
+                config,
+                true
+            )
+            .is_ok());
+        writer.as_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn print_template_footer_timestamp() {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![parser
+                .parse_commit_message("Added something", None)
+                .unwrap()],
+            message_ids: vec![],
+        };
+        let print = |once: bool, index_len: (usize, usize)| {
+            let template: Value = toml::from_str(&format!(
+                "[footer]\ntext = \"Generated at\"\nonce = {}\ninclude_timestamp = true",
+                once
+            ))
+            .unwrap();
+            let mut writer = Output::new_buffer();
+            tag.print_to_term_and_write_to_vector(
+                &mut writer,
+                true,
+                &parser.config,
+                Some(&template),
+                index_len,
+            )
+            .unwrap();
+            writer.as_str().unwrap().to_owned()
+        };
+        let output = print(false, (0, 2));
+        let today = Utc::today().format("%Y-%m-%d").to_string();
+        assert!(output.contains(&format!("\nGenerated at {} ", today)));
+        assert!(output.ends_with(" UTC\n"));
+        assert!(!print(true, (0, 2)).contains("Generated at"));
+        assert!(print(true, (1, 2)).contains("Generated at"));
+    }
+
+    #[test]
+    fn print_security_section() {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![
+                parser
+                    .parse_commit_message("Added something", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Fixed an overflow\n\nSecurity: CVE-1", None)
+                    .unwrap(),
+            ],
+            message_ids: vec![],
+        };
+        let mut config = Config::new();
+        config.colored_output = false;
+        let print = |config: &Config| {
+            let mut writer = Output::new_buffer();
+            assert!(tag
+                .print_to_term_and_write_to_vector(&mut writer, true, config, None, (0, 1))
+                .is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert!(!print(&config).contains("## Security"));
+
+        config.highlight_security = true;
+        let output = print(&config);
+        assert!(output.contains("\n## Security\n- [Fixed] an overflow (CVE-1)\n"));
+        assert!(output.find("## Security").unwrap() < output.find("[Added]").unwrap());
+    }
+
+    #[test]
+    fn print_commit_count() {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![
+                parser
+                    .parse_commit_message("Added something", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Fixed something :internal:", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Removed something", None)
+                    .unwrap(),
+            ],
+            message_ids: vec![],
+        };
+        let mut config = Config::new();
+        config.colored_output = false;
+        config.show_commit_count = true;
+        config.excluded_commit_tags = vec!["internal".to_owned()];
+        let mut writer = Output::new_buffer();
+        assert!(tag
+            .print_to_term_and_write_to_vector(&mut writer, true, &config, None, (0, 1))
+            .is_ok());
+        let output = writer.as_str().unwrap();
+        assert!(output.contains("\n# v1 (2020-01-02) (2 commits):"));
+        assert_eq!(output.matches("\n- ").count(), 2);
+    }
+
+    fn parse_timed_commits(messages: &[(&str, i64)]) -> ParsedTag {
+        let parser = get_parser();
+        ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: messages
+                .iter()
+                .map(|&(message, time)| {
+                    let mut commit = parser.parse_commit_message(message, None).unwrap();
+                    commit.time = Some(time);
+                    commit
+                })
+                .collect(),
+            message_ids: vec![],
+        }
+    }
+
+    fn summaries(tag: &ParsedTag) -> Vec<&str> {
+        tag.commits
+            .iter()
+            .map(|c| c.summary.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sort_commits_by_date() {
+        let mut tag = parse_timed_commits(&[
+            ("Fixed second", 2),
+            ("Added third", 3),
+            ("Changed first", 1),
+        ]);
+        let mut config = Config::new();
+        tag.sort_commits(&config);
+        assert_eq!(summaries(&tag), vec!["third", "second", "first"]);
+
+        config.sort_direction = "ascending".to_owned();
+        tag.sort_commits(&config);
+        assert_eq!(summaries(&tag), vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn sort_commits_by_name() {
+        let mut tag = parse_timed_commits(&[
+            ("Fixed the parser", 1),
+            ("Added some tests", 2),
+            ("Fixed a typo", 3),
+            ("Fixed my mistake", 4),
+        ]);
+        let mut config = Config::new();
+        config.sort_by = "name".to_owned();
+        tag.sort_commits(&config);
+        assert_eq!(
+            summaries(&tag),
+            vec!["some tests", "a typo", "my mistake", "the parser"]
+        );
+    }
+
+    #[test]
+    fn print_signature_status() {
+        let mut commit = get_parser()
+            .parse_commit_message("Added something", None)
+            .unwrap();
+        let mut config = Config::new();
+        config.colored_output = false;
+        let print = |commit: &ParsedCommit, config: &Config| {
+            let mut writer = Output::new_buffer();
+            assert!(commit
+                .summary
+                .print_default(&mut writer, config, None)
+                .is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(print(&commit, &config), "\n- [Added] something");
+
+        config.show_signature_status = true;
+        assert_eq!(print(&commit, &config), "\n- [Added] something ✗");
+
+        commit.summary.signed = true;
+        assert_eq!(
+            print(&commit, &config),
+            "\n- [Added] something ✓ (unverified)"
+        );
+    }
+
+    #[test]
+    fn print_notes() {
+        let mut commit = get_parser()
+            .parse_commit_message("Added something", None)
+            .unwrap();
+        commit.note = Some("Some release\ncontext".to_owned());
+        let mut config = Config::new();
+        config.colored_output = false;
+        let print = |config: &Config| {
+            let mut writer = Output::new_buffer();
+            assert!(commit.print_default(&mut writer, config, None).is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(print(&config), "\n- [Added] something");
+
+        config.show_notes = true;
+        assert_eq!(
+            print(&config),
+            "\n- [Added] something\n    Some release\n    context"
+        );
+    }
+
+    #[test]
+    fn print_indent_width() {
+        let commit = get_parser()
+            .parse_commit_message(
+                "Added something\n\n- A list item\n\nA paragraph\nwith two lines",
+                None,
+            )
+            .unwrap();
+        let mut config = Config::new();
+        config.colored_output = false;
+        let print = |config: &Config, tag: Option<&str>| {
+            let mut writer = Output::new_buffer();
+            assert!(commit.print_default(&mut writer, config, tag).is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            print(&config, None),
+            "\n- [Added] something\n    - A list item\n    A paragraph\n    with two lines"
+        );
+
+        config.indent_width = 2;
+        assert_eq!(
+            print(&config, None),
+            "\n- [Added] something\n  - A list item\n  A paragraph\n  with two lines"
+        );
+
+        config.indent_width = 4;
+        assert_eq!(
+            print(&config, Some("default")),
+            "\n- [Added] something\n- A list item\n- A paragraph\n  with two lines"
+        );
+    }
+
+    #[test]
+    fn print_bullet_char() {
+        let commit = get_parser()
+            .parse_commit_message("Added something\n\n- A list item\n\nA paragraph", None)
+            .unwrap();
+        let mut config = Config::new();
+        config.colored_output = false;
+        config.bullet_char = "*".to_owned();
+        let print = |tag: Option<&str>| {
+            let mut writer = Output::new_buffer();
+            assert!(commit.print_default(&mut writer, &config, tag).is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            print(None),
+            "\n* [Added] something\n    * A list item\n    A paragraph"
+        );
+        assert_eq!(
+            print(Some("default")),
+            "\n* [Added] something\n* A list item\n* A paragraph"
+        );
+    }
+
+    #[test]
+    fn print_gitlab_collapsible_sections() {
+        let mut config = Config::new();
+        config.colored_output = false;
+        let output = print_template_sections(&config);
+        assert!(!output.contains("<details>"));
+        assert!(output.contains("\n## Section <1>\n"));
+
+        config.document_format = "gitlab".to_owned();
+        let output = print_template_sections(&config);
+        assert_eq!(output.matches("<details>").count(), 2);
+        assert!(output.contains(
+            "\n<details>\n<summary>Section &lt;1&gt;</summary>\n\n- [Added] one <thing>\n\
+             - [Fixed] two things\n\n</details>\n"
+        ));
+
+        config.collapse_threshold = 1;
+        let output = print_template_sections(&config);
+        assert_eq!(output.matches("<details>").count(), 1);
+        assert!(output.contains("\n## Default\n- [Removed] something\n"));
+    }
+
+    #[test]
+    fn parse_commit_conventional() {
+        let mut parser = get_parser();
+        parser.config.commit_style = "conventional".to_owned();
+        parser.config.categories = vec!["feat".to_owned(), "fix".to_owned()];
+
+        let commit = parser
+            .parse_commit_message("JIRA-1234 fix(parser)!: handle scopes :tag1:", None)
+            .unwrap();
+        assert_eq!(commit.summary.prefix, "JIRA-1234");
+        assert_eq!(commit.summary.category, "fix");
+        assert_eq!(commit.summary.scope, Some("parser".to_owned()));
+        assert_eq!(commit.summary.text, "handle scopes");
+        assert_eq!(commit.summary.tags, vec!["tag1".to_owned()]);
+
+        let commit = parser
+            .parse_commit_message("feat: add something", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "feat");
+        assert_eq!(commit.summary.scope, None);
+
+        assert!(parser
+            .parse_commit_message("feat add something", None)
+            .is_err());
+        assert!(parser
+            .parse_commit_message("[feat] add something", None)
+            .is_err());
+    }
+
+    fn parse_error_offset(message: &str) -> usize {
+        get_parser()
+            .parse_commit_message(message, None)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+            .offset()
+    }
+
+    #[test]
+    fn parse_commit_failure_offset() {
+        assert_eq!(parse_error_offset("JIRA-1234 Changing stuff"), 10);
+        assert_eq!(parse_error_offset("  JIRA-1234 Addedstuff"), 12);
+        assert_eq!(parse_error_offset("Added"), 5);
+    }
+
+    #[test]
+    fn print_first_paragraph_as_subtitle() {
+        let message = fs::read_to_string("./tests/commit_messages/subtitle_1").unwrap();
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        let commit = parser.parse_commit_message(&message, None).unwrap();
+        assert_eq!(
+            commit.render(&parser.config, false),
+            "- [Added] the streaming API\n    \
+             For web interfaces which render the releases incrementally\n    \
+             - [Fixed] the memory usage"
+        );
+
+        parser.config.treat_first_paragraph_as_subtitle = true;
+        assert_eq!(
+            commit.render(&parser.config, false),
+            "- [Added] the streaming API — For web interfaces which render the releases \
+             incrementally\n    - [Fixed] the memory usage"
+        );
+        parser.config.subtitle_separator = ": ".to_owned();
+        assert!(commit
+            .render(&parser.config, false)
+            .starts_with("- [Added] the streaming API: For web interfaces"));
+
+        // Paragraphs with multiple lines stay separate blocks
+        let commit = parser
+            .parse_commit_message("Added the API\n\nFirst line\nsecond line", None)
+            .unwrap();
+        assert_eq!(
+            commit.render(&parser.config, false),
+            "- [Added] the API\n    First line\n    second line"
+        );
+    }
+
+    #[test]
+    fn parse_commit_expand_squash() {
+        let message = fs::read_to_string("./tests/commit_messages/squash_1").unwrap();
+        assert!(get_parser().parse_commit_message(&message, None).is_err());
+
+        let mut parser = get_parser();
+        parser.config.expand_squash_commits = true;
+        let commit = parser.parse_commit_message(&message, None).unwrap();
+        assert_eq!(commit.body.len(), 3);
+        assert_eq!(commit.footer.len(), 1);
+
+        let commits = commit.expand_squash();
+        assert_eq!(commits.len(), 3);
+        assert_eq!(commits[0].summary.category, "Added");
+        assert_eq!(commits[0].summary.text, "support for scopes");
+        assert_eq!(commits[0].summary.tags, vec!["parser".to_owned()]);
+        assert_eq!(commits[0].footer.len(), 1);
+        assert_eq!(commits[1].summary.category, "Fixed");
+        assert_eq!(commits[1].summary.text, "the offset calculation");
+        assert!(commits[1].footer.is_empty());
+        assert_eq!(commits[2].summary.category, "Improved");
+        assert!(commits.iter().all(|c| c.body.is_empty()));
+
+        let commit = get_parser()
+            .parse_commit_message("Added something\n\n- A list item\n- Fixed item", None)
+            .unwrap();
+        assert_eq!(commit.clone().expand_squash(), vec![commit]);
+    }
+
+    #[test]
+    fn parse_commit_without_category() {
+        let mut parser = get_parser();
+        assert!(parser
+            .parse_commit_message("JIRA-1234 Update the docs", None)
+            .is_err());
+
+        parser.config.require_category = false;
+        let commit = parser
+            .parse_commit_message("JIRA-1234 Update the docs", None)
+            .unwrap();
+        assert_eq!(commit.summary.prefix, "JIRA-1234");
+        assert_eq!(commit.summary.category, "");
+        assert_eq!(commit.summary.text, "Update the docs");
+
+        let commit = parser.parse_commit_message("Fixed the docs", None).unwrap();
+        assert_eq!(commit.summary.category, "Fixed");
+        assert_eq!(commit.summary.text, "the docs");
+    }
+
+    #[test]
+    fn parse_commit_footer_pattern() {
+        let message = "Fixed the offsets\n\nFixes #123\nCloses #456";
+        let mut parser = get_parser();
+        let commit = parser.parse_commit_message(message, None).unwrap();
+        assert!(commit.footer.is_empty());
+
+        parser.config.footer_pattern =
+            Some(FooterPattern::new(r"^(Fixes|Closes) (#\d+)$").unwrap());
+        let commit = parser.parse_commit_message(message, None).unwrap();
+        assert_eq!(commit.footer.len(), 2);
+        assert_eq!(commit.footer[0].key, "Fixes");
+        assert_eq!(commit.footer[0].value, "#123");
+        assert_eq!(commit.footer[1].key, "Closes");
+        assert!(commit.body.is_empty());
+
+        // Both groups have to participate in the match
+        parser.config.footer_pattern =
+            Some(FooterPattern::new(r"^(?:(Fixes)|Closes) (#\d+)$").unwrap());
+        let commit = parser.parse_commit_message(message, None).unwrap();
+        assert_eq!(commit.footer.len(), 1);
+        assert_eq!(commit.footer[0].key, "Fixes");
+    }
+
+    #[test]
+    fn parse_commit_category_scope() {
+        let mut parser = get_parser();
+        let commit = parser
+            .parse_commit_message("[Fixed:parser] the offsets :tag1:", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "Fixed");
+        assert_eq!(commit.summary.scope, Some("parser".to_owned()));
+        assert_eq!(commit.summary.text, "the offsets");
+
+        let commit = parser
+            .parse_commit_message("Fixed the offsets\n\n- [Added:cli] a flag", None)
+            .unwrap();
+        assert_eq!(commit.summary.scope, None);
+        match &commit.body[0] {
+            BodyElement::List(list) => {
+                assert_eq!(list[0].category, "Added");
+                assert_eq!(list[0].text, "a flag");
+            }
+            _ => panic!("Expected a list"),
+        }
+
+        parser.config.category_scope_separator = String::new();
+        assert!(parser
+            .parse_commit_message("[Fixed:parser] the offsets", None)
+            .is_err());
+    }
+
+    #[test]
+    fn parse_commit_category_word_boundary() {
+        let mut parser = get_parser();
+        parser.config.categories = vec!["Add".to_owned(), "Added".to_owned()];
+        assert!(parser
+            .parse_commit_message("Address the config issue", None)
+            .is_err());
+
+        let commit = parser.parse_commit_message("Add the docs", None).unwrap();
+        assert_eq!(commit.summary.category, "Add");
+        let commit = parser
+            .parse_commit_message("[Added] the docs", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "Added");
+
+        parser.config.require_category = false;
+        let commit = parser
+            .parse_commit_message("Address the config issue", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "");
+        assert_eq!(commit.summary.text, "Address the config issue");
+    }
+
+    #[test]
+    fn slug() {
+        assert_eq!(github_slug("v1.0.0 (2020-01-02):"), "v100-2020-01-02");
+        assert_eq!(
+            github_slug("Unreleased (2020-01-02) (1 commit):"),
+            "unreleased-2020-01-02-1-commit"
+        );
+        assert_eq!(github_slug("Some_Tag ÄÖ"), "some_tag-äö");
+    }
+
+    #[test]
+    fn print_toc() {
+        let commit = get_parser()
+            .parse_commit_message("Added something", None)
+            .unwrap();
+        let mut parser = get_parser();
+        for name in &["v2.0.0", "v1.0.0", "v1.0.0"] {
+            parser.result.push(ParsedTag {
+                name: (*name).to_owned(),
+                date: Utc.ymd(2020, 1, 2),
+                commits: vec![commit.clone()],
+                message_ids: vec![],
+            });
+        }
+        parser.config.colored_output = false;
+        parser.config.generate_toc = true;
+        let mut writer = Output::new_buffer();
+        assert!(parser.print(true, None, "markdown", &mut writer).is_ok());
+        let output = writer.as_str().unwrap();
+        assert!(output.starts_with(
+            "- [v2.0.0](#v200-2020-01-02)\n- [v1.0.0](#v100-2020-01-02)\n\
+             - [v1.0.0](#v100-2020-01-02-1)\n\n# v2.0.0 (2020-01-02):"
+        ));
+    }
+
+    #[test]
+    fn print_template_parallel() {
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        for index in 0..20 {
+            let commits = [
+                format!("Added feature {} :tag1:", index),
+                format!("Fixed bug {}\n\n- [Changed] something :tag2:", index),
+            ]
+            .iter()
+            .map(|message| parser.parse_commit_message(message, None).unwrap())
+            .collect();
+            parser.result.push(ParsedTag {
+                name: format!("v{}", index),
+                date: Utc.ymd(2020, 1, 2),
+                commits,
+                message_ids: vec![],
+            });
+        }
+
+        let print = |parser: &Parser| {
+            let mut writer = Output::new_buffer();
+            parser
+                .print(
+                    false,
+                    Some("./tests/template.toml"),
+                    "markdown",
+                    &mut writer,
+                )
+                .unwrap();
+            writer.as_str().unwrap().to_owned()
+        };
+        let parallel = print(&parser);
+        parser.config.render_parallel = false;
+        assert_eq!(parallel, print(&parser));
+        assert!(parallel.starts_with("\nSome header Markdown/HTML.\n"));
+        assert!(parallel.trim_end().ends_with("My footer"));
+        assert!(parallel.find("# v0 ").unwrap() < parallel.find("# v19 ").unwrap());
+    }
+
+    #[test]
+    fn print_title() {
+        let commit = get_parser()
+            .parse_commit_message("Added something", None)
+            .unwrap();
+        let mut parser = get_parser();
+        for name in &["v2.0.0", "v1.0.0"] {
+            parser.result.push(ParsedTag {
+                name: (*name).to_owned(),
+                date: Utc.ymd(2020, 1, 2),
+                commits: vec![commit.clone()],
+                message_ids: vec![],
+            });
+        }
+        parser.config.colored_output = false;
+        parser.config.generate_toc = true;
+        parser.config.title = Some("Release Notes".to_owned());
+
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        let output = writer.as_str().unwrap();
+        assert!(output.starts_with("# Release Notes\n- [v2.0.0]"));
+        assert_eq!(output.matches("Release Notes").count(), 1);
+
+        parser.config.document_format = "rst".to_owned();
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        let output = writer.as_str().unwrap();
+        assert!(output.starts_with("Release Notes\n=============\n"));
+        assert_eq!(output.matches("Release Notes").count(), 1);
+    }
+
+    #[test]
+    fn print_summary_wrapped() {
+        let commit = get_parser()
+            .parse_commit_message(
+                "Added a very long summary which does not fit into a single line of the \
+                 terminal at all",
+                Some(Oid::from_str("1234567890").unwrap()),
+            )
+            .unwrap();
+        let mut config = Config::new();
+        config.colored_output = false;
+        config.show_commit_hash = true;
+
+        let print = |config: &Config| {
+            let mut writer = Output::new_buffer();
+            assert!(commit
+                .summary
+                .print_default(&mut writer, config, None)
+                .is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            print(&config),
+            "\n- [Added] a very long summary which does not fit into a single line of the \
+             terminal at all (1234567)"
+        );
+
+        config.summary_wrap_width = Some(30);
+        let output = print(&config);
+        assert_eq!(
+            output,
+            "\n- [Added] a very long summary\n  which does not fit into a\n  single line of the \
+             terminal\n  at all (1234567)"
+        );
+        assert!(output.lines().all(|line| line.chars().count() <= 30));
+    }
+
+    #[test]
+    fn print_group_by_category() {
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        let commits = [
+            "Fixed a bug",
+            "Added a feature\n\n- With a list",
+            "Changed the docs",
+            "Added another feature",
+        ]
+        .iter()
+        .map(|message| parser.parse_commit_message(message, None).unwrap())
+        .collect();
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits,
+            message_ids: vec![],
+        });
+
+        let print = |parser: &Parser| {
+            let mut writer = Output::new_buffer();
+            parser.print(false, None, "markdown", &mut writer).unwrap();
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            print(&parser),
+            "\n# v1 (2020-01-02):\n- [Fixed] a bug\n- [Added] a feature\n    - With a list\n\
+             - [Changed] the docs\n- [Added] another feature\n"
+        );
+
+        parser.config.group_by_category = true;
+        assert_eq!(
+            print(&parser),
+            "\n# v1 (2020-01-02):\n\
+             ## Added\n- [Added] a feature\n    - With a list\n- [Added] another feature\n\
+             \n## Changed\n- [Changed] the docs\n\
+             \n## Fixed\n- [Fixed] a bug\n"
+        );
+    }
+
+    #[test]
+    fn print_always_expand_categories() {
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        parser.config.always_expand_categories = vec!["Removed".to_owned()];
+        let commits = [
+            "Removed the old API\n\nIt was deprecated since v1.",
+            "Fixed a bug\n\nIt was a typo.",
+        ]
+        .iter()
+        .map(|message| parser.parse_commit_message(message, None).unwrap())
+        .collect();
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits,
+            message_ids: vec![],
+        });
+
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "\n# v1 (2020-01-02):\n- [Removed] the old API\n    It was deprecated since v1\n\
+             - [Fixed] a bug\n"
+        );
+    }
+
+    #[test]
+    fn print_collapse_repeated_list_categories() {
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        parser.config.collapse_repeated_list_categories = true;
+        let commit = parser
+            .parse_commit_message(
+                "Changed the parser\n\n- [Fixed] the first bug\n- [Fixed] the second bug\n\
+                 - [Added] a feature\n- [Fixed] the third bug\n- Other item",
+                None,
+            )
+            .unwrap();
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![commit],
+            message_ids: vec![],
+        });
+
+        let mut writer = Output::new_buffer();
+        parser.print(false, None, "markdown", &mut writer).unwrap();
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "\n# v1 (2020-01-02):\n- [Changed] the parser\n    - [Fixed]\n        - the first bug\n\
+             \x20       - the second bug\n    - [Added]\n        - a feature\n    - [Fixed]\n\
+             \x20       - the third bug\n    - Other item\n"
+        );
+    }
+
+    #[test]
+    fn print_category_headings() {
+        let mut parser = get_parser();
+        parser.config.colored_output = false;
+        parser.config.group_by_category = true;
+        parser
+            .config
+            .category_headings
+            .insert("Added".to_owned(), "New Features".to_owned());
+        let commits = ["Added a feature\n\n- [Added] a list item", "Fixed a bug"]
+            .iter()
+            .map(|message| parser.parse_commit_message(message, None).unwrap())
+            .collect();
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits,
+            message_ids: vec![],
+        });
+
+        let mut writer = Output::new_buffer();
+        parser.print(false, None, "markdown", &mut writer).unwrap();
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "\n# v1 (2020-01-02):\n\
+             ## New Features\n- [New Features] a feature\n    - [New Features] a list item\n\
+             \n## Fixed\n- [Fixed] a bug\n"
+        );
+    }
+
+    #[test]
+    fn print_category_emoji() {
+        let mut parser = get_parser();
+        parser.config.show_category_emoji = true;
+        parser
+            .config
+            .category_emoji
+            .insert("Added".to_owned(), "✨".to_owned());
+        parser
+            .config
+            .category_emoji
+            .insert("Fixed".to_owned(), ":bug:".to_owned());
+        let commits = vec![
+            parser
+                .parse_commit_message("Added a feature", None)
+                .unwrap(),
+            parser.parse_commit_message("Fixed a bug", None).unwrap(),
+        ];
+        parser.result.push(ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits,
+            message_ids: vec![0, 1],
+        });
+
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        let output = writer.as_str().unwrap();
+        assert!(output.contains("\n- ✨ [Added] a feature"));
+        assert!(output.contains("\n- :bug: [Fixed] a bug"));
+
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "terminal", &mut writer).unwrap();
+        let output = writer.as_str().unwrap();
+        assert!(output.contains("\n- ✨ "));
+        assert!(output.contains("[Added] "));
+
+        // Emoji take two columns when wrapping the summary
+        parser.config.summary_wrap_width = Some(21);
+        let mut writer = Output::new_buffer();
+        parser.print(true, None, "markdown", &mut writer).unwrap();
+        assert!(writer
+            .as_str()
+            .unwrap()
+            .contains("\n- ✨ [Added] a\n  feature"));
+    }
+
+    #[test]
+    fn print_digest() {
+        let mut parser = get_parser();
+        parser.config.require_category = false;
+        for (name, messages) in &[
+            (
+                "v2.0.0",
+                &["Fixed a bug", "Added a feature", "Fixed another bug"][..],
+            ),
+            ("v1.0.0", &["Added the library", "Some other thing"][..]),
+        ] {
+            let commits = messages
+                .iter()
+                .map(|message| parser.parse_commit_message(message, None).unwrap())
+                .collect();
+            parser.result.push(ParsedTag {
+                name: (*name).to_owned(),
+                date: Utc.ymd(2020, 1, 2),
+                commits,
+                message_ids: vec![],
+            });
+        }
+
+        let mut writer = Output::new_buffer();
+        parser.print(false, None, "digest", &mut writer).unwrap();
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "v2.0.0 (2020-01-02): 3 commits (1 Added, 2 Fixed)\n\
+             v1.0.0 (2020-01-02): 2 commits (1 Added, 1 Other)\n"
+        );
+    }
+
+    #[test]
+    fn print_json_lines() {
+        let mut parser = get_parser();
+        for (name, messages) in &[
+            ("v2.0.0", &["Added something", "Fixed something"][..]),
+            (
+                "v1.0.0",
+                &["Removed something\n\n- Changed a list item"][..],
+            ),
+        ] {
+            let commits = messages
+                .iter()
+                .map(|message| {
+                    parser
+                        .parse_commit_message(message, Some(Oid::from_str("1234567890").unwrap()))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            parser.result.push(ParsedTag {
+                name: (*name).to_owned(),
+                date: Utc.ymd(2020, 1, 2),
+                commits,
+                message_ids: vec![],
+            });
+        }
+
+        let mut writer = Output::new_buffer();
+        assert!(parser.print(false, None, "json-lines", &mut writer).is_ok());
+        let output = writer.as_str().unwrap();
+        assert_eq!(output.lines().count(), 3);
+        for line in output.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(
+                value["commit"]["oid"],
+                "1234567890000000000000000000000000000000"
+            );
+        }
+        let last: serde_json::Value = serde_json::from_str(output.lines().last().unwrap()).unwrap();
+        assert_eq!(last["tag"], "v1.0.0");
+        assert_eq!(last["commit"]["summary"]["category"], "Removed");
+        assert_eq!(last["commit"]["body"][0]["List"][0]["text"], "a list item");
+    }
+
+    #[test]
+    fn print_plain() {
+        let mut parser = get_parser();
+        let commit = parser
+            .parse_commit_message(
+                "Added **bold** text\n\n- Fixed `code`\n\nReviewed-by: Me",
+                Some(Oid::from_str("1234567890").unwrap()),
+            )
+            .unwrap();
+        parser.result.push(ParsedTag {
+            name: "v1.0.0".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![commit],
+            message_ids: vec![],
+        });
+        parser.config.colored_output = true;
+
+        let mut writer = Output::new_buffer();
+        assert!(parser.print(false, None, "plain", &mut writer).is_ok());
+        let output = writer.as_str().unwrap();
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("- [Added] bold text"));
+        assert!(output.contains("- [Fixed] code"));
+    }
+
+    #[test]
+    fn print_rst() {
+        let mut parser = get_parser();
+        let commit = parser
+            .parse_commit_message(
+                "Added something\n\nA paragraph\nwith two lines\n\n- Fixed a list item",
+                Some(Oid::from_str("1234567890").unwrap()),
+            )
+            .unwrap();
+        parser.result.push(ParsedTag {
+            name: "v1.0.0".to_owned(),
+            date: Utc.ymd(2020, 1, 2),
+            commits: vec![commit],
+            message_ids: vec![],
+        });
+        parser.config.document_format = "rst".to_owned();
+        parser.config.show_commit_hash = true;
+
+        let mut writer = Output::new_buffer();
+        assert!(parser.print(false, None, "markdown", &mut writer).is_ok());
+        let output = writer.as_str().unwrap();
+        assert_eq!(
+            output,
+            "Changelog\n=========\n\n\
+             v1.0.0 (2020-01-02):\n--------------------\n\n\
+             * [Added] something (``1234567``)\n\n  \
+             A paragraph\n  with two lines\n\n  \
+             * [Fixed] a list item\n"
+        );
+    }
+
+    #[test]
+    fn print_footer_styles() {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![
+                parser
+                    .parse_commit_message("Added a\n\nReviewed-by: Me\nIssue: #1", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Fixed b\n\nIssue: #2 | #3", None)
+                    .unwrap(),
+            ],
+            message_ids: vec![],
+        };
+        let mut config = Config::new();
+        config.colored_output = false;
+        let print = |config: &Config| {
+            let mut writer = Output::new_buffer();
+            assert!(tag.print_footers(&mut writer, None, config).is_ok());
+            writer.as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            print(&config),
+            "\nIssue:\n#1, #2 | #3\n\nReviewed-by:\nMe\n"
+        );
+
+        config.footer_style = "bold".to_owned();
+        assert_eq!(
+            print(&config),
+            "\n**Issue:** #1, #2 | #3\n\n**Reviewed-by:** Me\n"
+        );
+
+        config.footer_style = "table".to_owned();
+        assert_eq!(
+            print(&config),
+            "\n| Footer | Values |\n| --- | --- |\n| Issue | #1, #2 \\| #3 |\n| Reviewed-by | Me |\n"
+        );
+    }
+
+    #[test]
+    fn print_footer_labels() {
+        let parser = get_parser();
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            date: Utc::today(),
+            commits: vec![
+                parser
+                    .parse_commit_message("Added a\n\nReviewed-by: Me\nIssue: #1", None)
+                    .unwrap(),
+                parser
+                    .parse_commit_message("Fixed b\n\nReviewed-by: You", None)
+                    .unwrap(),
+            ],
+            message_ids: vec![],
+        };
+        let mut config = Config::new();
+        config.colored_output = false;
+        config
+            .footer_labels
+            .insert("Reviewed-by".to_owned(), "Reviewed by".to_owned());
+        let mut writer = Output::new_buffer();
+        assert!(tag.print_footers(&mut writer, None, &config).is_ok());
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "\nIssue:\n#1\n\nReviewed by:\nMe, You\n"
+        );
+
+        // The footer keys of the template refer to the raw keys
+        let mut writer = Output::new_buffer();
+        let keys = [Value::String("Reviewed-by".to_owned())];
+        assert!(tag.print_footers(&mut writer, Some(&keys), &config).is_ok());
+        assert_eq!(writer.as_str().unwrap(), "\nReviewed by:\nMe, You\n");
+    }
+
+    #[test]
+    fn parse_commit_tab_indented_list() {
+        let message = fs::read_to_string("./tests/commit_messages/success_tabs").unwrap();
+        let commit = get_parser().parse_commit_message(&message, None).unwrap();
+        assert_eq!(commit.body.len(), 1);
+        if let BodyElement::List(ref list) = commit.body[0] {
+            assert_eq!(
+                list.iter()
+                    .map(|item| (item.level, item.text.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (0, "Item 1"),
+                    (1, "Item 1.1"),
+                    (2, "Item 1.1.1"),
+                    (1, "Item 1.2"),
+                    (0, "Item 2"),
+                ]
+            );
+        } else {
+            panic!("No list parsed");
+        }
+
+        let mut config = Config::new();
+        config.colored_output = false;
+        let mut writer = Output::new_buffer();
+        assert!(commit.print_default(&mut writer, &config, None).is_ok());
+        assert_eq!(
+            writer.as_str().unwrap(),
+            "\n- [Added] tab indented lists\n    - Item 1\n        - Item 1.1\n            \
+                 - Item 1.1.1\n        - Item 1.2\n    - Item 2"
+        );
+    }
+
     #[test]
     fn parse_commit_failure_1() {
         parse_and_print_error("None");
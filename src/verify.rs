@@ -0,0 +1,613 @@
+//! The commit message verification, which works without any git repository,
+//! and its structured results, which can be used by editor integrations to
+//! display the problems inline.
+
+use crate::{
+    config::{resolve_template, Config},
+    parser::{ParseError, ParsedCommit, Parser, Tags},
+    TEMPLATE_ENV,
+};
+use failure::Error;
+use log::warn;
+use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::HashSet, env, fmt, fs, path::Path};
+
+/// The overall result of a commit message verification.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyReport {
+    /// True if no error level problems were found
+    pub valid: bool,
+
+    /// All problems found within the commit message
+    pub problems: Vec<VerifyProblem>,
+}
+
+impl VerifyReport {
+    /// Constructs a new valid `VerifyReport` without any problems.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            valid: true,
+            problems: vec![],
+        }
+    }
+
+    /// Adds a problem to the report, where error level problems mark it as
+    /// invalid and disabled ones are dropped.
+    pub fn push(&mut self, problem: VerifyProblem) {
+        match problem.severity {
+            Severity::Off => return,
+            Severity::Error => self.valid = false,
+            Severity::Warn => {}
+        }
+        self.problems.push(problem);
+    }
+
+    /// Returns all problems of a given severity.
+    pub fn problems_with(&self, severity: Severity) -> impl Iterator<Item = &VerifyProblem> {
+        self.problems.iter().filter(move |p| p.severity == severity)
+    }
+}
+
+impl Default for VerifyReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single problem found during the commit message verification.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyProblem {
+    /// The kind of the problem
+    pub kind: ProblemKind,
+
+    /// The severity of the problem
+    pub severity: Severity,
+
+    /// A human readable description of the problem
+    pub message: String,
+
+    /// The line of the problem (starting at 1), if known
+    pub line: Option<usize>,
+
+    /// The column of the problem (starting at 1), if known
+    pub column: Option<usize>,
+}
+
+impl VerifyProblem {
+    /// Constructs a new `VerifyProblem` with the default severity of its kind
+    /// and without a known position.
+    pub fn new(kind: ProblemKind, message: &str) -> Self {
+        Self {
+            kind,
+            severity: kind.default_severity(),
+            message: message.to_owned(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Sets the position of the problem to the first occurrence of `needle`
+    /// within the commit message, if any.
+    pub fn at_first(mut self, commit_message: &str, needle: &str) -> Self {
+        for (index, line) in commit_message.lines().enumerate() {
+            if let Some(column) = line.find(needle) {
+                self.line = Some(index + 1);
+                self.column = Some(line[..column].chars().count() + 1);
+                break;
+            }
+        }
+        self
+    }
+
+    /// Sets the position of the problem to the given byte offset within the
+    /// commit message.
+    pub fn at_offset(mut self, commit_message: &str, offset: usize) -> Self {
+        let before = &commit_message[..offset.min(commit_message.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        self.line = Some(before.matches('\n').count() + 1);
+        self.column = Some(before[line_start..].chars().count() + 1);
+        self
+    }
+}
+
+impl fmt::Display for VerifyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " (line {}, column {})", line, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// The severity of a verification problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The problem will be reported, but the commit message stays valid
+    Warn,
+
+    /// The problem invalidates the commit message
+    Error,
+
+    /// The problem will not be reported at all
+    Off,
+}
+
+/// The different kinds of verification problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// The commit message could not be parsed
+    Parse,
+
+    /// A tag of the commit message is not part of the default template
+    UnknownTag,
+
+    /// A word of the summary is not part of the spell check word list
+    Spelling,
+
+    /// The summary text does not start in imperative mood
+    ImperativeMood,
+
+    /// The commit category requires a body, but there is none
+    MissingBody,
+
+    /// The commit message contains a disallowed word or phrase
+    DisallowedPhrase,
+
+    /// The commit message body has too many or too long lines
+    BodyLength,
+
+    /// The category is not part of the configured ones, which can happen if
+    /// it is overridden by a trailer
+    UnknownCategory,
+}
+
+impl ProblemKind {
+    /// The name of the kind, which is used within the configuration as well.
+    pub fn name(self) -> &'static str {
+        match self {
+            ProblemKind::Parse => "parse",
+            ProblemKind::UnknownTag => "unknown_tag",
+            ProblemKind::Spelling => "spelling",
+            ProblemKind::ImperativeMood => "imperative_mood",
+            ProblemKind::MissingBody => "missing_body",
+            ProblemKind::DisallowedPhrase => "disallowed_phrase",
+            ProblemKind::BodyLength => "body_length",
+            ProblemKind::UnknownCategory => "unknown_category",
+        }
+    }
+
+    /// The severity of the kind if nothing else is configured.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            ProblemKind::Spelling | ProblemKind::ImperativeMood | ProblemKind::UnknownCategory => {
+                Severity::Warn
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Verifies commit messages against the configured rules without the need of
+/// a git repository, where the files of the configuration like the default
+/// template or the spell check word list are read only once.
+pub struct Verifier {
+    /// The parser of the commit messages, which holds the configuration
+    parser: Parser,
+
+    /// The tags of the default template, if configured
+    template_tags: Option<Vec<String>>,
+
+    /// The lowercase words of the spell check word list, if configured
+    words: Option<HashSet<String>>,
+
+    /// The compiled disallowed phrases
+    disallowed_phrases: Vec<Regex>,
+}
+
+impl Verifier {
+    /// Constructs a new `Verifier`, where the relative paths of the
+    /// configuration are resolved within the given `path`. The default
+    /// template falls back to the one of the `GIT_JOURNAL_TEMPLATE`
+    /// environment variable.
+    ///
+    /// # Errors
+    /// When reading the default template or the spell check word list failed,
+    /// or a disallowed phrase is not a valid regular expression.
+    pub fn new(config: &Config, path: &Path) -> Result<Self, Error> {
+        let parser = Parser::new(config.clone());
+
+        let template_tags = match resolve_template(
+            path,
+            config.default_template.as_deref(),
+            env::var_os(TEMPLATE_ENV),
+        ) {
+            Some(path_buf) => {
+                let toml = toml::from_str(&fs::read_to_string(path_buf)?)?;
+                Some(parser.get_tags_from_toml(&toml, vec![]))
+            }
+            None => None,
+        };
+
+        let words = match config.spellcheck_wordlist {
+            Some(ref wordlist) => Some(
+                fs::read_to_string(path.join(wordlist))?
+                    .lines()
+                    .map(|word| word.trim().to_lowercase())
+                    .filter(|word| !word.is_empty())
+                    .collect(),
+            ),
+            None => None,
+        };
+
+        let disallowed_phrases = config
+            .disallowed_phrases
+            .iter()
+            .map(|phrase| disallowed_phrase_regex(phrase, config.disallowed_phrases_regex))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            parser,
+            template_tags,
+            words,
+            disallowed_phrases,
+        })
+    }
+
+    /// Verifies a commit message against the parsing rules of
+    /// [RFC0001](https://github.com/saschagrunert/git-journal/blob/master/rfc/0001-commit-msg.md)
+    /// and the configured checks, where all found problems are collected in
+    /// the report. The report is only invalid if an error level problem
+    /// exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{verify::Verifier, Config};
+    /// use std::path::Path;
+    ///
+    /// let verifier = Verifier::new(&Config::new(), Path::new(".")).unwrap();
+    /// let report = verifier.verify_message("Added the verification\n\n- [Fixed] a list item");
+    /// assert!(report.valid);
+    /// ```
+    pub fn verify_message(&self, commit_message: &str) -> VerifyReport {
+        // The positions of the problems do not include a leading BOM
+        let commit_message = commit_message.trim_start_matches('\u{feff}');
+
+        let mut report = VerifyReport::new();
+
+        // Banned phrases are reported even if the message is not parsable
+        self.check_disallowed_phrases(commit_message, &mut report);
+        if self.parser.config.max_body_lines.is_some()
+            || self.parser.config.max_body_line_length.is_some()
+        {
+            self.check_body_length(commit_message, &mut report);
+        }
+
+        // Parse the commit and extract the tags
+        let parsed_commit = match self.parser.parse_commit_message(commit_message, None) {
+            Ok(parsed_commit) => parsed_commit,
+            Err(e) => {
+                let mut problem = VerifyProblem::new(ProblemKind::Parse, &e.to_string());
+                if let Some(parse_error) = e.downcast_ref::<ParseError>() {
+                    problem = problem.at_offset(commit_message, parse_error.offset());
+                }
+                self.push_problem(&mut report, problem);
+                return report;
+            }
+        };
+        let tags = parsed_commit.get_tags_unique(vec![]);
+
+        // Overridden categories have to be configured ones as well
+        let category = &parsed_commit.summary.category;
+        if parsed_commit.category_overridden && !self.parser.is_category(category) {
+            self.push_problem(
+                &mut report,
+                VerifyProblem::new(
+                    ProblemKind::UnknownCategory,
+                    &format!("Category '{}' is not part of the categories.", category),
+                )
+                .at_first(commit_message, category),
+            );
+        }
+
+        // Check if the category needs a detailed description
+        if parsed_commit.body.is_empty() && self.parser.config.require_body_for.contains(category) {
+            self.push_problem(
+                &mut report,
+                VerifyProblem::new(
+                    ProblemKind::MissingBody,
+                    &format!("Commits of category '{}' require a body.", category),
+                )
+                .at_first(commit_message, category),
+            );
+        }
+
+        // Check if the tags within the commit also occur in the default
+        // template and report them if not.
+        if let Some(ref template_tags) = self.template_tags {
+            let invalid_tags = tags
+                .into_iter()
+                .filter(|tag| !template_tags.contains(tag))
+                .collect::<Vec<String>>();
+            if !invalid_tags.is_empty() {
+                warn!(
+                    "These tags are not part of the default template: '{}'.",
+                    invalid_tags.join(", ")
+                );
+            }
+            for tag in invalid_tags {
+                let mut problem = VerifyProblem::new(
+                    ProblemKind::UnknownTag,
+                    &format!("Tag '{}' does not exist in the default template.", tag),
+                )
+                .at_first(commit_message, &format!(":{}:", tag));
+                if problem.line.is_none() {
+                    problem = problem.at_first(commit_message, &tag);
+                }
+                self.push_problem(&mut report, problem);
+            }
+        }
+
+        // Spell check the summary text if a word list is configured
+        if let Some(ref words) = self.words {
+            self.check_spelling(words, &parsed_commit, commit_message, &mut report);
+        }
+
+        // Check the mood of the first word after the category
+        if self.parser.config.imperative_mood_check {
+            self.check_imperative_mood(&parsed_commit, commit_message, &mut report);
+        }
+        report
+    }
+
+    fn check_body_length(&self, commit_message: &str, report: &mut VerifyReport) {
+        // Comments will be removed by git anyway
+        let body = commit_message
+            .lines()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, line)| !self.parser.config.is_comment_line(line))
+            .skip_while(|(_, line)| line.trim().is_empty())
+            .collect::<Vec<_>>();
+        let body_len = body
+            .iter()
+            .rposition(|(_, line)| !line.trim().is_empty())
+            .map_or(0, |last| last + 1);
+
+        if let Some(max_lines) = self.parser.config.max_body_lines {
+            if body_len > max_lines {
+                let mut problem = VerifyProblem::new(
+                    ProblemKind::BodyLength,
+                    &format!(
+                        "Body has {} lines, but only {} are allowed.",
+                        body_len, max_lines
+                    ),
+                );
+                problem.line = Some(body[max_lines].0 + 1);
+                problem.column = Some(1);
+                self.push_problem(report, problem);
+            }
+        }
+
+        if let Some(max_length) = self.parser.config.max_body_line_length {
+            if let Some((index, line)) = body[..body_len]
+                .iter()
+                .find(|(_, line)| line.chars().count() > max_length)
+            {
+                let mut problem = VerifyProblem::new(
+                    ProblemKind::BodyLength,
+                    &format!(
+                        "Body line has {} characters, but only {} are allowed.",
+                        line.chars().count(),
+                        max_length
+                    ),
+                );
+                problem.line = Some(index + 1);
+                problem.column = Some(max_length + 1);
+                self.push_problem(report, problem);
+            }
+        }
+    }
+
+    fn check_disallowed_phrases(&self, commit_message: &str, report: &mut VerifyReport) {
+        for regex in &self.disallowed_phrases {
+            // Comments will be removed by git anyway
+            let mut offset = 0;
+            for line in commit_message.split_inclusive('\n') {
+                if !self.parser.config.is_comment_line(line) {
+                    if let Some(found) = regex.find(line) {
+                        self.push_problem(
+                            report,
+                            VerifyProblem::new(
+                                ProblemKind::DisallowedPhrase,
+                                &format!("Disallowed phrase '{}' found.", found.as_str()),
+                            )
+                            .at_offset(commit_message, offset + found.start()),
+                        );
+                        break;
+                    }
+                }
+                offset += line.len();
+            }
+        }
+    }
+
+    fn check_imperative_mood(
+        &self,
+        parsed_commit: &ParsedCommit,
+        commit_message: &str,
+        report: &mut VerifyReport,
+    ) {
+        let word = match parsed_commit.summary.text.split_whitespace().next() {
+            Some(word) => word.trim_matches(|c: char| !c.is_alphanumeric()),
+            None => return,
+        };
+        let lowercase_word = word.to_lowercase();
+        if self
+            .parser
+            .config
+            .imperative_mood_exceptions
+            .iter()
+            .any(|exception| exception.to_lowercase() == lowercase_word)
+        {
+            return;
+        }
+        if let Some(suffix) = self
+            .parser
+            .config
+            .imperative_mood_suffixes
+            .iter()
+            .find(|suffix| lowercase_word.ends_with(&suffix.to_lowercase()))
+        {
+            let first_line = commit_message.lines().next().unwrap_or("");
+            self.push_problem(
+                report,
+                VerifyProblem::new(
+                    ProblemKind::ImperativeMood,
+                    &format!(
+                        "Summary should start in imperative mood, but '{}' ends with '{}'.",
+                        word, suffix
+                    ),
+                )
+                .at_first(first_line, word),
+            );
+        }
+    }
+
+    fn check_spelling(
+        &self,
+        words: &HashSet<String>,
+        parsed_commit: &ParsedCommit,
+        commit_message: &str,
+        report: &mut VerifyReport,
+    ) {
+        // The prefix, category and tags are not part of the summary text
+        let mut unknown_words = vec![];
+        for word in parsed_commit
+            .summary
+            .text
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .map(|word| word.trim_matches('\''))
+            .filter(|word| !word.is_empty() && !word.chars().any(char::is_numeric))
+        {
+            if !words.contains(&word.to_lowercase()) && !unknown_words.contains(&word) {
+                unknown_words.push(word);
+            }
+        }
+
+        let first_line = commit_message.lines().next().unwrap_or("");
+        for word in unknown_words {
+            let problem = VerifyProblem::new(
+                ProblemKind::Spelling,
+                &format!("Word '{}' is not part of the spell check word list.", word),
+            )
+            .at_first(first_line, word);
+            self.push_problem(report, problem);
+        }
+    }
+
+    /// Adds a problem to the report with its configured severity
+    fn push_problem(&self, report: &mut VerifyReport, mut problem: VerifyProblem) {
+        problem.severity = self.parser.config.get_severity(problem.kind);
+        report.push(problem);
+    }
+}
+
+/// Compiles a disallowed phrase, which is matched case insensitive as a whole
+/// word unless it is a regular expression on its own.
+#[allow(clippy::unnecessary_map_or)]
+fn disallowed_phrase_regex(phrase: &str, is_regex: bool) -> Result<Regex, regex::Error> {
+    if is_regex {
+        return Regex::new(phrase);
+    }
+
+    // Word boundaries only apply next to word characters, like for the
+    // `fixup!` prefix
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let boundary = |c: Option<char>| if c.map_or(false, is_word) { r"\b" } else { "" };
+    Regex::new(&format!(
+        "(?i){}{}{}",
+        boundary(phrase.chars().next()),
+        regex::escape(phrase),
+        boundary(phrase.chars().last())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_push() {
+        let mut report = VerifyReport::new();
+        report.push(VerifyProblem::new(ProblemKind::Spelling, "Warning"));
+        assert!(report.valid);
+        assert_eq!(report.problems_with(Severity::Warn).count(), 1);
+
+        let mut problem = VerifyProblem::new(ProblemKind::Parse, "Disabled");
+        problem.severity = Severity::Off;
+        report.push(problem);
+        assert!(report.valid);
+        assert_eq!(report.problems.len(), 1);
+
+        report.push(VerifyProblem::new(ProblemKind::Parse, "Failure"));
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems.len(), 2);
+        assert_eq!(report.problems_with(Severity::Error).count(), 1);
+    }
+
+    #[test]
+    fn problem_kind_name() {
+        assert_eq!(
+            serde_json::to_string(&ProblemKind::ImperativeMood).unwrap(),
+            format!("\"{}\"", ProblemKind::ImperativeMood.name())
+        );
+    }
+
+    #[test]
+    fn problem_position() {
+        let problem = VerifyProblem::new(ProblemKind::UnknownTag, "Unknown tag")
+            .at_first("Added something\n\n- [Fixed] 💖 :tag1:", ":tag1:");
+        assert_eq!(problem.line, Some(3));
+        assert_eq!(problem.column, Some(13));
+        assert_eq!(
+            problem.to_string(),
+            "Unknown tag (line 3, column 13)".to_owned()
+        );
+
+        let problem = VerifyProblem::new(ProblemKind::UnknownTag, "Unknown tag")
+            .at_first("Added something", ":tag1:");
+        assert_eq!(problem.line, None);
+        assert_eq!(problem.to_string(), "Unknown tag".to_owned());
+
+        let problem = VerifyProblem::new(ProblemKind::Parse, "Parse error")
+            .at_offset("Added something\n\n💖 Paragraph", 21);
+        assert_eq!(problem.line, Some(3));
+        assert_eq!(problem.column, Some(2));
+    }
+
+    #[test]
+    fn verifier_without_repository() {
+        let mut config = Config::new();
+        config.require_body_for = vec!["Removed".to_owned()];
+        config.spellcheck_wordlist = Some("tests/wordlist".to_owned());
+        let verifier = Verifier::new(&config, Path::new(".")).unwrap();
+        assert!(verifier.verify_message("Added something").valid);
+
+        let report = verifier.verify_message("Removed something");
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems[0].kind, ProblemKind::MissingBody);
+
+        let report = verifier.verify_message("Something without a category");
+        assert_eq!(report.valid, false);
+        assert_eq!(report.problems[0].kind, ProblemKind::Parse);
+
+        config.spellcheck_wordlist = Some("tests/missing_wordlist".to_owned());
+        assert!(Verifier::new(&config, Path::new(".")).is_err());
+    }
+}